@@ -0,0 +1,340 @@
+use std::fmt;
+
+use super::token::{CellRef, RangeBound, Span, StructuredRef, Token, column_name};
+use crate::functions::describe_arity;
+
+/// Parser error types
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    UnexpectedToken(Box<Token>, Span),
+    UnexpectedEnd(Span),
+    RaggedArray {
+        row: usize, // 1-based
+        expected: usize,
+        found: usize,
+        span: Span,
+    },
+    WrongArgumentCount {
+        name: String,
+        min: usize,
+        max: Option<usize>,
+        repeat: usize,
+        found: usize,
+        span: Span,
+    },
+    ExpectedReference {
+        name: String,
+        position: usize, // 1-based
+        span: Span,
+    },
+}
+
+impl ParseError {
+    /// Location of the offending input
+    pub fn span(&self) -> Span {
+        match self {
+            Self::UnexpectedToken(_, span)
+            | Self::UnexpectedEnd(span)
+            | Self::RaggedArray { span, .. }
+            | Self::WrongArgumentCount { span, .. }
+            | Self::ExpectedReference { span, .. } => *span,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedToken(token, _) => write!(f, "unexpected '{token}'"),
+            Self::UnexpectedEnd(_) => write!(f, "unexpected end of formula"),
+            Self::RaggedArray {
+                row,
+                expected,
+                found,
+                ..
+            } => write!(
+                f,
+                "array row {row} has {found} value{}, expected {expected} like the first row",
+                if *found == 1 { "" } else { "s" }
+            ),
+            Self::WrongArgumentCount {
+                name,
+                min,
+                max,
+                repeat,
+                ..
+            } => write!(f, "{name} expects {}", describe_arity(*min, *max, *repeat)),
+            Self::ExpectedReference { name, position, .. } => {
+                write!(f, "{name} argument {position} must be a reference")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Constant values
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Number(f64),
+    String(String),
+    Bool(bool),
+    Error(String), // #N/A, #REF!, ...
+}
+
+/// Prefix operators
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Plus,
+    Negate,
+}
+
+/// Infix operators on values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Power,
+    Concat,
+    Equal,
+    NotEqual,
+    Less,
+    Greater,
+    LessEqual,
+    GreaterEqual,
+}
+
+impl BinaryOp {
+    /// The operator as written in a formula
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Add => "+",
+            Self::Subtract => "-",
+            Self::Multiply => "*",
+            Self::Divide => "/",
+            Self::Power => "^",
+            Self::Concat => "&",
+            Self::Equal => "=",
+            Self::NotEqual => "<>",
+            Self::Less => "<",
+            Self::Greater => ">",
+            Self::LessEqual => "<=",
+            Self::GreaterEqual => ">=",
+        }
+    }
+}
+
+/// How a function call was classified by the lexer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallKind {
+    Builtin,
+    Reference,            // INDEX, OFFSET, INDIRECT
+    ConditionalReference, // IF, CHOOSE
+    UserDefined,
+}
+
+/// The sheet part of a qualified reference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SheetRef {
+    Single(String),        // Sheet2!, 'Q1 Budget'!
+    Range(String, String), // Jan:Dec!
+}
+
+/// What a reference points at, without its workbook and sheet prefix
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReferenceKind {
+    Cell(CellRef),
+    Rows(RangeBound, RangeBound),    // 1:5
+    Columns(RangeBound, RangeBound), // A:C
+    Name(String),
+    ReservedName(String),
+    Structured(StructuredRef),
+    Dde {
+        application: String,
+        topic: String,
+        item: Option<String>,
+    },
+}
+
+/// A possibly workbook- and sheet-qualified reference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    pub workbook: Option<u32>,
+    pub sheet: Option<SheetRef>,
+    pub kind: ReferenceKind,
+}
+
+impl Reference {
+    /// Unqualified reference on the current sheet
+    pub fn local(kind: ReferenceKind) -> Self {
+        Self {
+            workbook: None,
+            sheet: None,
+            kind,
+        }
+    }
+}
+
+/// Formula expression tree
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Literal(Literal),
+    Reference(Reference),
+    Range(Box<Expr>, Box<Expr>),     // A1:B2, INDEX(A:A,3):B5
+    Intersect(Box<Expr>, Box<Expr>), // A1:C3 B2:D4
+    Union(Vec<Expr>),                // (A1:A3,C1:C3)
+    Unary {
+        op: UnaryOp,
+        operand: Box<Expr>,
+    },
+    Percent(Box<Expr>),
+    Binary {
+        op: BinaryOp,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+    Call {
+        name: String,
+        kind: CallKind,
+        args: Vec<Expr>,
+    },
+    Array(Vec<Vec<Expr>>),
+    Missing, // omitted argument, as in IF(A1,,1)
+}
+
+impl Expr {
+    /// Whether the expression can evaluate to a reference rather than only
+    /// a value, e.g. `A1:B2` or `INDEX(A:A,3)` but not `{1,2}` or `A1+1`
+    pub fn is_reference(&self) -> bool {
+        match self {
+            Expr::Reference(_) | Expr::Range(..) | Expr::Intersect(..) | Expr::Union(_) => true,
+            Expr::Call { kind, .. } => {
+                matches!(kind, CallKind::Reference | CallKind::ConditionalReference)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn write_sheet_name(f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+    if name
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '_' || c == '.')
+    {
+        write!(f, "{name}")
+    } else {
+        write!(f, "'{}'", name.replace('\'', "''"))
+    }
+}
+
+impl fmt::Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(workbook) = self.workbook {
+            write!(f, "[{workbook}]")?;
+        }
+        match &self.sheet {
+            Some(SheetRef::Single(name)) => {
+                write_sheet_name(f, name)?;
+                write!(f, "!")?;
+            }
+            Some(SheetRef::Range(first, last)) => {
+                write_sheet_name(f, first)?;
+                write!(f, ":")?;
+                write_sheet_name(f, last)?;
+                write!(f, "!")?;
+            }
+            None if self.workbook.is_some() => write!(f, "!")?,
+            None => {}
+        }
+
+        let bound = |bound: &RangeBound, text: String| {
+            if bound.absolute {
+                format!("${text}")
+            } else {
+                text
+            }
+        };
+        match &self.kind {
+            ReferenceKind::Cell(cell) => write!(f, "{cell}"),
+            ReferenceKind::Rows(first, last) => write!(
+                f,
+                "{}:{}",
+                bound(first, first.index.to_string()),
+                bound(last, last.index.to_string())
+            ),
+            ReferenceKind::Columns(first, last) => write!(
+                f,
+                "{}:{}",
+                bound(first, column_name(first.index)),
+                bound(last, column_name(last.index))
+            ),
+            ReferenceKind::Name(name) => write!(f, "{name}"),
+            ReferenceKind::ReservedName(name) => write!(f, "_xlnm.{name}"),
+            ReferenceKind::Structured(reference) => write!(f, "{reference}"),
+            ReferenceKind::Dde {
+                application,
+                topic,
+                item,
+            } => {
+                write!(f, "{application}|'{}'", topic.replace('\'', "''"))?;
+                match item {
+                    Some(item) => write!(f, "!{item}"),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// Renders the expression back as a formula (without the leading `=`).
+///
+/// Operators are fully parenthesized so the tree shape is visible, e.g.
+/// `-2^2` renders as `((-2)^2)`.
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |items: &[Expr], sep: &str| {
+            items
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(sep)
+        };
+        match self {
+            Expr::Literal(Literal::Number(n)) => write!(f, "{n}"),
+            Expr::Literal(Literal::String(s)) => write!(f, "\"{}\"", s.replace('"', "\"\"")),
+            Expr::Literal(Literal::Bool(b)) => write!(f, "{}", if *b { "TRUE" } else { "FALSE" }),
+            Expr::Literal(Literal::Error(e)) => write!(f, "{e}"),
+            Expr::Reference(reference) => write!(f, "{reference}"),
+            Expr::Range(left, right) => match (left.as_ref(), right.as_ref()) {
+                // The parser copies the sheet onto the right end; print it once
+                (Expr::Reference(start), Expr::Reference(end))
+                    if start.workbook == end.workbook && start.sheet == end.sheet =>
+                {
+                    write!(f, "{start}:{}", Reference::local(end.kind.clone()))
+                }
+                _ => write!(f, "{left}:{right}"),
+            },
+            Expr::Intersect(left, right) => write!(f, "({left} {right})"),
+            Expr::Union(items) => write!(f, "({})", join(items, ",")),
+            Expr::Unary { op, operand } => {
+                let symbol = match op {
+                    UnaryOp::Plus => "+",
+                    UnaryOp::Negate => "-",
+                };
+                write!(f, "({symbol}{operand})")
+            }
+            Expr::Percent(operand) => write!(f, "({operand}%)"),
+            Expr::Binary { op, left, right } => {
+                write!(f, "({left}{}{right})", op.symbol())
+            }
+            Expr::Call { name, args, .. } => write!(f, "{name}({})", join(args, ",")),
+            Expr::Array(rows) => {
+                let rows: Vec<String> = rows.iter().map(|row| join(row, ",")).collect();
+                write!(f, "{{{}}}", rows.join(";"))
+            }
+            Expr::Missing => Ok(()),
+        }
+    }
+}
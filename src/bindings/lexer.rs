@@ -1,18 +1,79 @@
-use super::token::{LexerError, Token};
+use super::builtins::{self, FunctionKind};
+use super::token::{
+    CellRef, LexerError, MAX_ROW, RangeBound, Span, Spanned, StructuredRef, TableItem, Token,
+    column_index,
+};
+
+/// Prefix of Excel's reserved names such as `_xlnm.Print_Area`
+const RESERVED_NAME_PREFIX: &str = "_XLNM.";
 
 pub struct Lexer {
     input: Vec<char>,
     position: usize,
+    // Byte offset of each char, plus the total length
+    byte_offsets: Vec<usize>,
+    // Char positions at which each line starts
+    line_starts: Vec<usize>,
+    // Char position where the token being lexed starts
+    token_start: usize,
+    // Second token of `'[1]Sheet 1'`, which lexes as FILE then SHEET-QUOTED,
+    // with its start position
+    pending: Option<(Token, usize)>,
+    // Whether the last token can be the left operand of an intersection
+    after_reference: bool,
 }
 
 impl Lexer {
     pub fn new(input: &str) -> Self {
+        let input: Vec<char> = input.chars().collect();
+        let mut byte_offsets = Vec::with_capacity(input.len() + 1);
+        let mut line_starts = vec![0];
+        let mut offset = 0;
+        for (i, c) in input.iter().enumerate() {
+            byte_offsets.push(offset);
+            offset += c.len_utf8();
+            if *c == '\n' {
+                line_starts.push(i + 1);
+            }
+        }
+        byte_offsets.push(offset);
+
         Self {
-            input: input.chars().collect(),
+            input,
             position: 0,
+            byte_offsets,
+            line_starts,
+            token_start: 0,
+            pending: None,
+            after_reference: false,
+        }
+    }
+
+    /// Span covering the chars `start..end`
+    fn span(&self, start: usize, end: usize) -> Span {
+        let line = self
+            .line_starts
+            .partition_point(|&line_start| line_start <= start);
+        Span {
+            start: self.byte_offsets[start],
+            end: self.byte_offsets[end],
+            char_start: start,
+            char_end: end,
+            line,
+            column: start - self.line_starts[line - 1] + 1,
         }
     }
 
+    /// Span of the token read so far
+    fn token_span(&self) -> Span {
+        self.span(self.token_start, self.position)
+    }
+
+    /// Span of the char at the current position
+    fn char_span(&self) -> Span {
+        self.span(self.position, (self.position + 1).min(self.input.len()))
+    }
+
     fn current(&self) -> Option<char> {
         self.input.get(self.position).copied()
     }
@@ -38,7 +99,7 @@ impl Lexer {
     fn read_number(&mut self) -> Result<f64, LexerError> {
         let start = self.position;
 
-        // Read integer part: [0-9]+
+        // Read integer part: [0-9]* (may be empty for `.5`)
         while let Some(c) = self.current() {
             if c.is_ascii_digit() {
                 self.advance();
@@ -59,9 +120,13 @@ impl Lexer {
             }
         }
 
-        // Read scientific notation part: (e [0-9]+)?
+        // Read scientific notation part: (e [+-]? [0-9]+)?
         if self.current() == Some('e') || self.current() == Some('E') {
             self.advance();
+            if self.current() == Some('+') || self.current() == Some('-') {
+                self.advance();
+            }
+            let digits = self.position;
             while let Some(c) = self.current() {
                 if c.is_ascii_digit() {
                     self.advance();
@@ -69,12 +134,21 @@ impl Lexer {
                     break;
                 }
             }
+            if self.position == digits {
+                let num_str = self.input[start..self.position].iter().collect();
+                return Err(LexerError::MissingExponentDigits(
+                    num_str,
+                    self.token_span(),
+                ));
+            }
         }
 
         let num_str: String = self.input[start..self.position].iter().collect();
-        num_str
-            .parse()
-            .map_err(|_| LexerError::InvalidNumber(num_str))
+        // Excel cannot represent values beyond f64, e.g. `1e999`
+        match num_str.parse::<f64>() {
+            Ok(num) if num.is_finite() => Ok(num),
+            _ => Err(LexerError::InvalidNumber(num_str, self.token_span())),
+        }
     }
 
     fn read_string(&mut self) -> Result<String, LexerError> {
@@ -102,7 +176,7 @@ impl Lexer {
         }
 
         // If we reach here, the string was not terminated
-        Err(LexerError::UnterminatedString)
+        Err(LexerError::UnterminatedString(self.token_span()))
     }
 
     fn read_error(&mut self) -> String {
@@ -120,11 +194,114 @@ impl Lexer {
         self.input[start..self.position].iter().collect()
     }
 
+    /// Returns the first position at or after `pos` whose char fails `pred`
+    fn scan_while(&self, mut pos: usize, pred: impl Fn(char) -> bool) -> usize {
+        while self.input.get(pos).is_some_and(|&c| pred(c)) {
+            pos += 1;
+        }
+        pos
+    }
+
+    /// Scans an optionally `$`-prefixed run of chars matching `pred`,
+    /// returning the position past it when the run is non-empty.
+    fn scan_bound(&self, start: usize, pred: impl Fn(char) -> bool) -> Option<usize> {
+        let pos = if self.input.get(start) == Some(&'$') {
+            start + 1
+        } else {
+            start
+        };
+        let end = self.scan_while(pos, pred);
+        (end > pos).then_some(end)
+    }
+
+    /// Checks whether a CELL token `$? [A-Z]+ $? [1-9][0-9]*` starts at `start`.
+    ///
+    /// Only the shape is checked here; returns the position just past the
+    /// reference without consuming any input.
+    fn scan_cell(&self, start: usize) -> Option<usize> {
+        let letters = self.scan_bound(start, |c| c.is_ascii_alphabetic())?;
+        let end = self.scan_bound(letters, |c| c.is_ascii_digit())?;
+
+        // `A1B` or `A1_x` are identifiers, not cells
+        if self.input.get(end).is_some_and(|&c| is_identifier_char(c)) {
+            return None;
+        }
+        Some(end)
+    }
+
+    /// Checks whether a `bound : bound` range whose bounds match `pred`
+    /// starts at `start`, returning the position past it.
+    fn scan_range(&self, start: usize, pred: impl Fn(char) -> bool + Copy) -> Option<usize> {
+        let first = self.scan_bound(start, pred)?;
+        if self.input.get(first) != Some(&':') {
+            return None;
+        }
+        let end = self.scan_bound(first + 1, pred)?;
+
+        // `1:5.5`, `A:B2` or `A:Bx` are not whole-row/column ranges
+        if self.input.get(end).is_some_and(|&c| is_identifier_char(c)) {
+            return None;
+        }
+        Some(end)
+    }
+
+    /// Reads a range matched by [`Self::scan_range`], converting each bound
+    /// with `index` (row numbers or column letters).
+    fn read_range(
+        &mut self,
+        end: usize,
+        index: impl Fn(&str) -> Option<u32>,
+    ) -> Result<(RangeBound, RangeBound), LexerError> {
+        let text: String = self.input[self.position..end].iter().collect();
+        self.position = end;
+
+        let bound = |part: &str| {
+            let digits = part.trim_start_matches('$');
+            index(digits).map(|index| RangeBound {
+                index,
+                absolute: part.starts_with('$'),
+            })
+        };
+        let (first, last) = text.split_once(':').unwrap_or((&text, ""));
+        match (bound(first), bound(last)) {
+            (Some(first), Some(last)) => Ok((first, last)),
+            _ => Err(LexerError::InvalidRange(text, self.token_span())),
+        }
+    }
+
+    /// Reads a cell reference previously matched by [`Self::scan_cell`] and
+    /// validates it against Excel's sheet limits.
+    fn read_cell(&mut self, end: usize) -> Result<CellRef, LexerError> {
+        let text: String = self.input[self.position..end].iter().collect();
+        self.position = end;
+
+        let absolute_column = text.starts_with('$');
+        let rest = text.trim_start_matches('$');
+        let split = rest
+            .find(|c: char| c == '$' || c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let (letters, rest) = rest.split_at(split);
+        let absolute_row = rest.starts_with('$');
+        let digits = rest.trim_start_matches('$');
+
+        let column = column_index(letters);
+        let row = row_index(digits);
+        match (column, row) {
+            (Some(column), Some(row)) => Ok(CellRef {
+                column,
+                row,
+                absolute_column,
+                absolute_row,
+            }),
+            _ => Err(LexerError::InvalidCellReference(text, self.token_span())),
+        }
+    }
+
     fn read_identifier(&mut self) -> String {
         let start = self.position;
 
         while let Some(c) = self.current() {
-            if c.is_alphanumeric() || c == '_' {
+            if is_identifier_char(c) {
                 self.advance();
             } else {
                 break;
@@ -134,8 +311,439 @@ impl Lexer {
         self.input[start..self.position].iter().collect()
     }
 
-    pub fn next_token(&mut self) -> Result<Token, LexerError> {
+    /// Checks whether a name immediately followed by `delimiter` starts at
+    /// `start`, returning the position of the delimiter.
+    ///
+    /// Used for function calls (`SUM(`) and table names (`Sales[`).
+    fn scan_name_before(&self, start: usize, delimiter: char) -> Option<usize> {
+        let first = self.input.get(start).copied()?;
+        if !is_name_start(first) {
+            return None;
+        }
+        let end = self.scan_while(start + 1, is_identifier_char);
+        (self.input.get(end) == Some(&delimiter)).then_some(end)
+    }
+
+    /// Reads a function name and its opening parenthesis, classifying it
+    /// against the built-in catalog.
+    fn read_function(&mut self, end: usize) -> Token {
+        let name: String = self.input[self.position..end].iter().collect();
+        self.position = end + 1; // skip (
+
+        match builtins::lookup(&name) {
+            Some((name, FunctionKind::Ordinary)) => Token::ExcelFunction(name),
+            Some((name, FunctionKind::Reference)) => Token::RefFunction(name),
+            Some((name, FunctionKind::ConditionalReference)) => Token::RefFunctionCond(name),
+            None => Token::Udf(name),
+        }
+    }
+
+    /// Reads a SHEET or MULTIPLE-SHEETS prefix (`Sheet2`, `Jan:Dec`).
+    ///
+    /// Only matches when the names are directly followed by `!`, which is
+    /// left for the next call to emit as [`Token::Exclamation`].
+    fn read_sheet(&mut self) -> Option<Token> {
+        let first_end = self.scan_while(self.position, is_sheet_char);
+        if first_end == self.position {
+            return None;
+        }
+        let end = match self.input.get(first_end) {
+            Some('!') => first_end,
+            Some(':') => {
+                let end = self.scan_while(first_end + 1, is_sheet_char);
+                if end == first_end + 1 || self.input.get(end) != Some(&'!') {
+                    return None;
+                }
+                end
+            }
+            _ => return None,
+        };
+
+        let first: String = self.input[self.position..first_end].iter().collect();
+        let token = if end == first_end {
+            Token::Sheet(first)
+        } else {
+            let last = self.input[first_end + 1..end].iter().collect();
+            Token::MultipleSheets(first, last)
+        };
+        self.position = end;
+        Some(token)
+    }
+
+    /// Reads a SHEET-QUOTED or MULTIPLE-SHEETS-QUOTED prefix (`'Q1 Budget'`,
+    /// `'Jan 2020:Dec 2020'`), unescaping doubled `''` quotes.
+    fn read_quoted_sheet(&mut self) -> Result<Token, LexerError> {
+        self.advance(); // skip opening '
+        let file = self.read_file().map(|index| (index, self.position));
+        let mut names = vec![String::new()];
+
+        loop {
+            match self.current() {
+                None => return Err(LexerError::UnterminatedSheetName(self.token_span())),
+                Some('\'') if self.peek(1) == Some('\'') => {
+                    names.last_mut().unwrap().push('\'');
+                    self.advance();
+                    self.advance();
+                }
+                Some('\'') => {
+                    self.advance(); // skip closing '
+                    break;
+                }
+                Some(':') if names.len() == 1 => {
+                    names.push(String::new());
+                    self.advance();
+                }
+                Some(c) if is_quoted_sheet_char(c) => {
+                    names.last_mut().unwrap().push(c);
+                    self.advance();
+                }
+                Some(c) => return Err(LexerError::UnexpectedChar(c, self.char_span())),
+            }
+        }
+
+        if names.iter().any(String::is_empty) {
+            return Err(LexerError::UnexpectedChar('\'', self.token_span()));
+        }
+        match self.current() {
+            Some('!') => {}
+            Some(c) => return Err(LexerError::UnexpectedChar(c, self.char_span())),
+            None => return Err(LexerError::UnexpectedChar('\'', self.token_span())),
+        }
+        let first = names.remove(0);
+        let sheet = match names.pop() {
+            Some(last) => Token::MultipleSheetsQuoted(first, last),
+            None => Token::SheetQuoted(first),
+        };
+        match file {
+            Some((index, sheet_start)) => {
+                self.pending = Some((sheet, sheet_start));
+                Ok(Token::File(index))
+            }
+            None => Ok(sheet),
+        }
+    }
+
+    /// Reads a FILE prefix `[1]` naming an external workbook by its index in
+    /// the workbook's external link table. Consumes nothing on mismatch.
+    fn read_file(&mut self) -> Option<u32> {
+        if self.current() != Some('[') {
+            return None;
+        }
+        let end = self.scan_while(self.position + 1, |c| c.is_ascii_digit());
+        if end == self.position + 1 || self.input.get(end) != Some(&']') {
+            return None;
+        }
+        let digits: String = self.input[self.position + 1..end].iter().collect();
+        let index = digits.parse().ok()?;
+        self.position = end + 1;
+        Some(index)
+    }
+
+    /// Reads the single-quoted part of a DDE call, unescaping `''`
+    fn read_dde_quoted(&mut self) -> Result<String, LexerError> {
+        match self.current() {
+            Some('\'') => self.advance(),
+            Some(c) => return Err(LexerError::UnexpectedChar(c, self.char_span())),
+            None => return Err(LexerError::UnterminatedDdeCall(self.token_span())),
+        }
+        let mut text = String::new();
+        loop {
+            match self.current() {
+                None => return Err(LexerError::UnterminatedDdeCall(self.token_span())),
+                Some('\'') if self.peek(1) == Some('\'') => {
+                    text.push('\'');
+                    self.advance();
+                    self.advance();
+                }
+                Some('\'') => {
+                    self.advance();
+                    return Ok(text);
+                }
+                Some(c) => {
+                    text.push(c);
+                    self.advance();
+                }
+            }
+        }
+    }
+
+    /// Reads a DDE call `application|'topic'!item` whose application name
+    /// ends at `end` (the `|`). The `!item` part is optional.
+    fn read_dde_call(&mut self, end: usize) -> Result<Token, LexerError> {
+        let application = self.input[self.position..end].iter().collect();
+        self.position = end + 1; // skip |
+        let topic = self.read_dde_quoted()?;
+
+        let item = if self.current() == Some('!') {
+            self.advance();
+            if self.current() == Some('\'') {
+                Some(self.read_dde_quoted()?)
+            } else {
+                let start = self.position;
+                self.position = self.scan_while(start, is_identifier_char);
+                if self.position == start {
+                    return Err(match self.current() {
+                        Some(c) => LexerError::UnexpectedChar(c, self.char_span()),
+                        None => LexerError::UnterminatedDdeCall(self.token_span()),
+                    });
+                }
+                Some(self.input[start..self.position].iter().collect())
+            }
+        } else {
+            None
+        };
+
+        Ok(Token::DdeCall {
+            application,
+            topic,
+            item,
+        })
+    }
+
+    /// Consumes `expected` inside a structured reference
+    fn expect_table_char(&mut self, expected: char) -> Result<(), LexerError> {
+        match self.current() {
+            Some(c) if c == expected => {
+                self.advance();
+                Ok(())
+            }
+            Some(c) => Err(LexerError::UnexpectedChar(c, self.char_span())),
+            None => Err(LexerError::UnterminatedStructuredReference(
+                self.token_span(),
+            )),
+        }
+    }
+
+    /// Reads an SR-COLUMN name up to (not including) its closing `]`.
+    ///
+    /// A `'` escapes the next character, so `Price'[USD']` is `Price[USD]`.
+    fn read_table_column(&mut self) -> Result<String, LexerError> {
+        let mut name = String::new();
+        loop {
+            match self.current() {
+                None => {
+                    return Err(LexerError::UnterminatedStructuredReference(
+                        self.token_span(),
+                    ));
+                }
+                Some(']') => break,
+                Some('\'') => {
+                    self.advance();
+                    let escaped = self.current().ok_or_else(|| {
+                        LexerError::UnterminatedStructuredReference(self.token_span())
+                    })?;
+                    name.push(escaped);
+                    self.advance();
+                }
+                Some(c @ ('[' | '#')) => {
+                    return Err(LexerError::UnexpectedChar(c, self.char_span()));
+                }
+                Some(c) => {
+                    name.push(c);
+                    self.advance();
+                }
+            }
+        }
+        if name.trim().is_empty() {
+            return Err(LexerError::InvalidStructuredReference(
+                name,
+                self.token_span(),
+            ));
+        }
+        Ok(name)
+    }
+
+    /// Reads a special item such as `#This Row` up to its closing `]`
+    fn read_table_item(&mut self) -> Result<TableItem, LexerError> {
+        let start = self.position;
+        let end = self.scan_while(start, |c| c != ']');
+        self.position = end;
+        if end == self.input.len() {
+            return Err(LexerError::UnterminatedStructuredReference(
+                self.token_span(),
+            ));
+        }
+
+        let text: String = self.input[start..end].iter().collect();
+        TableItem::from_name(&text[1..])
+            .ok_or_else(|| LexerError::InvalidStructuredReference(text, self.token_span()))
+    }
+
+    /// Reads the comma-separated `[#Item]` / `[Column]` / `[First]:[Last]`
+    /// specifiers of the bracketed structured reference form.
+    fn read_table_specifiers(&mut self, reference: &mut StructuredRef) -> Result<(), LexerError> {
+        loop {
+            self.skip_whitespace();
+            self.expect_table_char('[')?;
+            if self.current() == Some('#') {
+                let item = self.read_table_item()?;
+                reference.items.push(item);
+                self.expect_table_char(']')?;
+            } else {
+                if reference.column.is_some() {
+                    let span = self.span(self.position - 1, self.position);
+                    return Err(LexerError::UnexpectedChar('[', span));
+                }
+                reference.column = Some(self.read_table_column()?);
+                self.expect_table_char(']')?;
+
+                self.skip_whitespace();
+                if self.current() == Some(':') {
+                    self.advance();
+                    self.skip_whitespace();
+                    self.expect_table_char('[')?;
+                    reference.last_column = Some(self.read_table_column()?);
+                    self.expect_table_char(']')?;
+                }
+            }
+
+            self.skip_whitespace();
+            match self.current() {
+                Some(',') => self.advance(),
+                Some(']') => return Ok(()),
+                Some(c) => return Err(LexerError::UnexpectedChar(c, self.char_span())),
+                None => {
+                    return Err(LexerError::UnterminatedStructuredReference(
+                        self.token_span(),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Reads the bracketed part of a structured reference, starting at `[`
+    fn read_structured_ref(&mut self, table: Option<String>) -> Result<StructuredRef, LexerError> {
+        self.advance(); // skip [
+        let mut reference = StructuredRef {
+            table,
+            ..Default::default()
+        };
+
+        match self.current() {
+            Some(']') => {}
+            Some('[') | Some(' ') => self.read_table_specifiers(&mut reference)?,
+            Some('@') => {
+                self.advance();
+                reference.items.push(TableItem::ThisRow);
+                match self.current() {
+                    Some(']') => {}
+                    Some('[') => {
+                        self.advance();
+                        reference.column = Some(self.read_table_column()?);
+                        self.expect_table_char(']')?;
+                    }
+                    _ => reference.column = Some(self.read_table_column()?),
+                }
+            }
+            Some('#') => {
+                let item = self.read_table_item()?;
+                reference.items.push(item);
+            }
+            _ => reference.column = Some(self.read_table_column()?),
+        }
+
+        self.expect_table_char(']')?;
+        Ok(reference)
+    }
+
+    /// Reads a token starting with a letter, `_`, `\` or `$`: function calls,
+    /// whole-column ranges, cells, booleans and names.
+    fn read_reference(&mut self, c: char) -> Result<Token, LexerError> {
+        let start = self.position;
+        // Out-of-bounds shapes without `$` (`Sales:Costs`, `XFE1`) are names
+        let is_name = |lexer: &Self, end: usize, err: LexerError| {
+            if c != '$' && !lexer.input[start..end].contains(&'$') {
+                Ok(())
+            } else {
+                Err(err)
+            }
+        };
+
+        if let Some(end) = self.scan_name_before(start, '(') {
+            return Ok(self.read_function(end));
+        }
+        if let Some(end) = self.scan_name_before(start, '|') {
+            return self.read_dde_call(end);
+        }
+        if let Some(end) = self.scan_name_before(start, '[') {
+            let table = self.input[start..end].iter().collect();
+            self.position = end;
+            let reference = self.read_structured_ref(Some(table))?;
+            return Ok(Token::StructuredRef(reference));
+        }
+        if let Some(end) = self.scan_range(start, |c| c.is_ascii_alphabetic()) {
+            match self.read_range(end, column_index) {
+                Ok((first, last)) => return Ok(Token::VerticalRange(first, last)),
+                Err(err) => is_name(self, end, err)?,
+            }
+            self.position = start;
+        }
+        if let Some(end) = self.scan_cell(start) {
+            match self.read_cell(end) {
+                Ok(cell) => return Ok(Token::Cell(cell)),
+                Err(err) => is_name(self, end, err)?,
+            }
+            self.position = start;
+        }
+        if c == '$' {
+            return Err(LexerError::UnexpectedChar(c, self.char_span()));
+        }
+
+        let ident = self.read_identifier();
+        let upper = ident.to_uppercase();
+        match upper.as_str() {
+            "TRUE" => Ok(Token::Bool(true)),
+            "FALSE" => Ok(Token::Bool(false)),
+            _ => match upper.strip_prefix(RESERVED_NAME_PREFIX) {
+                Some(rest) if !rest.is_empty() => Ok(Token::ReservedName(
+                    ident[RESERVED_NAME_PREFIX.len()..].to_string(),
+                )),
+                _ => Ok(Token::NamedRange(ident)),
+            },
+        }
+    }
+
+    /// Speculatively lexes the next token to see whether it can be the right
+    /// operand of an intersection. Consumes nothing.
+    fn at_reference_start(&mut self) -> bool {
+        let (position, token_start) = (self.position, self.token_start);
+        let next = self.lex_token();
+        (self.position, self.token_start) = (position, token_start);
+        self.pending = None;
+        next.is_ok_and(|token| starts_reference(&token))
+    }
+
+    pub fn next_token(&mut self) -> Result<Spanned<Token>, LexerError> {
+        let token = self.lex_token()?;
+        // A FILE read from `'[1]Sheet'` ends where its pending sheet starts
+        let end = self
+            .pending
+            .as_ref()
+            .map_or(self.position, |(_, start)| *start);
+        self.after_reference = ends_reference(&token);
+        Ok(Spanned {
+            value: token,
+            span: self.span(self.token_start, end),
+        })
+    }
+
+    fn lex_token(&mut self) -> Result<Token, LexerError> {
+        if let Some((token, start)) = self.pending.take() {
+            self.token_start = start;
+            return Ok(token);
+        }
+
+        // Whitespace between two references is the intersection operator
+        self.token_start = self.position;
         self.skip_whitespace();
+        if self.position > self.token_start && self.after_reference && self.at_reference_start() {
+            return Ok(Token::Intersect);
+        }
+        self.token_start = self.position;
+
+        if let Some(sheet) = self.read_sheet() {
+            return Ok(sheet);
+        }
 
         match self.current() {
             None => Ok(Token::Eof),
@@ -159,6 +767,10 @@ impl Lexer {
                 self.advance();
                 Ok(Token::Power)
             }
+            Some('%') => {
+                self.advance();
+                Ok(Token::Percent)
+            }
             Some('&') => {
                 self.advance();
                 Ok(Token::Concatenate)
@@ -191,6 +803,14 @@ impl Lexer {
                     _ => Ok(Token::Greater),
                 }
             }
+            Some('(') => {
+                self.advance();
+                Ok(Token::LeftParen)
+            }
+            Some(')') => {
+                self.advance();
+                Ok(Token::RightParen)
+            }
             Some('{') => {
                 self.advance();
                 Ok(Token::LeftBrace)
@@ -207,6 +827,22 @@ impl Lexer {
                 self.advance();
                 Ok(Token::Semicolon)
             }
+            Some(':') => {
+                self.advance();
+                Ok(Token::Colon)
+            }
+            Some('!') => {
+                self.advance();
+                Ok(Token::Exclamation)
+            }
+            Some('\'') => self.read_quoted_sheet(),
+            Some('[') => {
+                if let Some(index) = self.read_file() {
+                    return Ok(Token::File(index));
+                }
+                let reference = self.read_structured_ref(None)?;
+                Ok(Token::StructuredRef(reference))
+            }
             Some('"') => {
                 let s = self.read_string()?;
                 Ok(Token::String(s))
@@ -221,27 +857,31 @@ impl Lexer {
                     Ok(Token::Error(format!("#{}", err)))
                 }
             }
-            Some(c) if c.is_ascii_digit() => {
+            Some('.') if self.peek(1).is_some_and(|c| c.is_ascii_digit()) => {
                 let num = self.read_number()?;
                 Ok(Token::Number(num))
             }
-            Some(c) if c.is_alphabetic() => {
-                let ident = self.read_identifier();
-                match ident.to_uppercase().as_str() {
-                    "TRUE" => Ok(Token::Bool(true)),
-                    "FALSE" => Ok(Token::Bool(false)),
-                    _ => Err(LexerError::UnexpectedChar(c)),
+            Some(c) if c == '$' || c.is_ascii_digit() => {
+                if let Some(end) = self.scan_range(self.position, |c| c.is_ascii_digit()) {
+                    let (first, last) = self.read_range(end, row_index)?;
+                    return Ok(Token::HorizontalRange(first, last));
+                }
+                if c == '$' {
+                    return self.read_reference(c);
                 }
+                let num = self.read_number()?;
+                Ok(Token::Number(num))
             }
-            Some(c) => Err(LexerError::UnexpectedChar(c)),
+            Some(c) if is_name_start(c) => self.read_reference(c),
+            Some(c) => Err(LexerError::UnexpectedChar(c, self.char_span())),
         }
     }
 
-    pub fn tokenize(&mut self) -> Result<Vec<Token>, LexerError> {
+    pub fn tokenize(&mut self) -> Result<Vec<Spanned<Token>>, LexerError> {
         let mut tokens = Vec::new();
         loop {
             let token = self.next_token()?;
-            let is_eof = matches!(token, Token::Eof);
+            let is_eof = matches!(token.value, Token::Eof);
             tokens.push(token);
             if is_eof {
                 break;
@@ -250,3 +890,70 @@ impl Lexer {
         Ok(tokens)
     }
 }
+
+/// Tokens that can end the left operand of an intersection
+fn ends_reference(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::Cell(_)
+            | Token::HorizontalRange(..)
+            | Token::VerticalRange(..)
+            | Token::NamedRange(_)
+            | Token::ReservedName(_)
+            | Token::StructuredRef(_)
+            | Token::RightParen
+    )
+}
+
+/// Tokens that can start the right operand of an intersection
+fn starts_reference(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::Cell(_)
+            | Token::HorizontalRange(..)
+            | Token::VerticalRange(..)
+            | Token::NamedRange(_)
+            | Token::ReservedName(_)
+            | Token::StructuredRef(_)
+            | Token::Sheet(_)
+            | Token::SheetQuoted(_)
+            | Token::MultipleSheets(..)
+            | Token::MultipleSheetsQuoted(..)
+            | Token::File(_)
+            | Token::RefFunction(_)
+            | Token::RefFunctionCond(_)
+            | Token::LeftParen
+    )
+}
+
+fn row_index(digits: &str) -> Option<u32> {
+    digits
+        .parse::<u32>()
+        .ok()
+        .filter(|row| (1..=MAX_ROW).contains(row))
+}
+
+/// Sheet characters (★2): anything but quotes, operators and delimiters
+fn is_sheet_char(c: char) -> bool {
+    !c.is_whitespace() && !c.is_control() && !"'*[]\\:/?();{}#\"=<>&+-^%,!".contains(c)
+}
+
+/// Enclosed sheet characters (★3), allowed between single quotes
+fn is_quoted_sheet_char(c: char) -> bool {
+    !c.is_control() && !"'*[]\\:/?".contains(c)
+}
+
+/// Extended characters (★1): non-control Unicode characters from x80 up
+fn is_extended_char(c: char) -> bool {
+    c as u32 >= 0x80 && !c.is_control() && !c.is_whitespace()
+}
+
+/// First character of a name or function: `[A-Z_\\★1]`
+fn is_name_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || c == '\\' || is_extended_char(c)
+}
+
+/// Valid named range characters (★4): `[A-Z0-9\\_.?★1]`
+fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "\\_.?".contains(c) || is_extended_char(c)
+}
@@ -1,2 +1,5 @@
+pub mod ast;
+pub mod builtins;
 pub mod lexer;
+pub mod parser;
 pub mod token;
@@ -0,0 +1,426 @@
+use super::ast::{
+    BinaryOp, CallKind, Expr, Literal, ParseError, Reference, ReferenceKind, SheetRef, UnaryOp,
+};
+use super::token::{Span, Spanned, Token};
+use crate::functions::{self, Arg};
+
+// Binding powers, from loosest to tightest. Binary operators are
+// left-associative: their right binding power is one above the left.
+const COMPARISON_BP: u8 = 1;
+const CONCAT_BP: u8 = 3;
+const ADDITIVE_BP: u8 = 5;
+const MULTIPLICATIVE_BP: u8 = 7;
+const POWER_BP: u8 = 9;
+const PERCENT_BP: u8 = 11;
+const PREFIX_BP: u8 = 12;
+const INTERSECT_BP: u8 = 13;
+const RANGE_BP: u8 = 15;
+
+/// Infix operators and their left binding power
+enum Infix {
+    Binary(BinaryOp),
+    Intersect,
+    Range,
+}
+
+fn infix(token: &Token) -> Option<(Infix, u8)> {
+    let op = match token {
+        Token::Equal => BinaryOp::Equal,
+        Token::NotEqual => BinaryOp::NotEqual,
+        Token::Less => BinaryOp::Less,
+        Token::Greater => BinaryOp::Greater,
+        Token::LessEqual => BinaryOp::LessEqual,
+        Token::GreaterEqual => BinaryOp::GreaterEqual,
+        Token::Concatenate => BinaryOp::Concat,
+        Token::Plus => BinaryOp::Add,
+        Token::Minus => BinaryOp::Subtract,
+        Token::Multiply => BinaryOp::Multiply,
+        Token::Divide => BinaryOp::Divide,
+        Token::Power => BinaryOp::Power,
+        Token::Intersect => return Some((Infix::Intersect, INTERSECT_BP)),
+        Token::Colon => return Some((Infix::Range, RANGE_BP)),
+        _ => return None,
+    };
+    let bp = match op {
+        BinaryOp::Equal
+        | BinaryOp::NotEqual
+        | BinaryOp::Less
+        | BinaryOp::Greater
+        | BinaryOp::LessEqual
+        | BinaryOp::GreaterEqual => COMPARISON_BP,
+        BinaryOp::Concat => CONCAT_BP,
+        BinaryOp::Add | BinaryOp::Subtract => ADDITIVE_BP,
+        BinaryOp::Multiply | BinaryOp::Divide => MULTIPLICATIVE_BP,
+        BinaryOp::Power => POWER_BP,
+    };
+    Some((Infix::Binary(op), bp))
+}
+
+/// Pratt parser turning the lexer's tokens into an [`Expr`] tree.
+///
+/// Precedence follows Excel, tightest first: `:`, intersection, negation,
+/// `%`, `^`, `*` `/`, `+` `-`, `&`, comparisons. All binary operators are
+/// left-associative, so `2^3^2` is `(2^3)^2` and `-2^2` is `(-2)^2`.
+pub struct Parser {
+    tokens: Vec<Spanned<Token>>,
+    position: usize,
+}
+
+impl Parser {
+    pub fn new(tokens: Vec<Spanned<Token>>) -> Self {
+        Self {
+            tokens,
+            position: 0,
+        }
+    }
+
+    fn current(&self) -> &Spanned<Token> {
+        // tokenize() always ends with Eof, which is never consumed
+        &self.tokens[self.position.min(self.tokens.len() - 1)]
+    }
+
+    fn advance(&mut self) -> Spanned<Token> {
+        let token = self.current().clone();
+        if !matches!(token.value, Token::Eof) {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn unexpected(token: Spanned<Token>) -> ParseError {
+        match token.value {
+            Token::Eof => ParseError::UnexpectedEnd(token.span),
+            other => ParseError::UnexpectedToken(Box::new(other), token.span),
+        }
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<Span, ParseError> {
+        let token = self.advance();
+        if token.value == expected {
+            Ok(token.span)
+        } else {
+            Err(Self::unexpected(token))
+        }
+    }
+
+    /// Parses a whole formula, with or without its leading `=`
+    pub fn parse(&mut self) -> Result<Expr, ParseError> {
+        if self.tokens.is_empty() {
+            return Err(ParseError::UnexpectedEnd(Span::default()));
+        }
+        if matches!(self.current().value, Token::Equal) {
+            self.advance();
+        }
+        let expr = self.parse_expr(0)?;
+        self.expect(Token::Eof)?;
+        Ok(expr)
+    }
+
+    fn parse_expr(&mut self, min_bp: u8) -> Result<Expr, ParseError> {
+        let mut left = self.parse_prefix()?;
+
+        loop {
+            if matches!(self.current().value, Token::Percent) {
+                if PERCENT_BP < min_bp {
+                    break;
+                }
+                self.advance();
+                left = Expr::Percent(Box::new(left));
+                continue;
+            }
+
+            let Some((op, bp)) = infix(&self.current().value) else {
+                break;
+            };
+            if bp < min_bp {
+                break;
+            }
+            self.advance();
+            let right = self.parse_expr(bp + 1)?;
+            left = match op {
+                Infix::Binary(op) => Expr::Binary {
+                    op,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+                Infix::Intersect => Expr::Intersect(Box::new(left), Box::new(right)),
+                Infix::Range => range(left, right),
+            };
+        }
+
+        Ok(left)
+    }
+
+    fn parse_prefix(&mut self) -> Result<Expr, ParseError> {
+        let token = self.advance();
+        let expr = match token.value {
+            Token::Plus | Token::Minus => {
+                let op = if token.value == Token::Plus {
+                    UnaryOp::Plus
+                } else {
+                    UnaryOp::Negate
+                };
+                let operand = self.parse_expr(PREFIX_BP)?;
+                Expr::Unary {
+                    op,
+                    operand: Box::new(operand),
+                }
+            }
+            Token::Number(n) => Expr::Literal(Literal::Number(n)),
+            Token::String(s) => Expr::Literal(Literal::String(s)),
+            Token::Bool(b) => Expr::Literal(Literal::Bool(b)),
+            Token::Error(e) => Expr::Literal(Literal::Error(e)),
+            Token::ErrorRef => Expr::Literal(Literal::Error("#REF!".to_string())),
+            Token::File(index) => self.parse_qualified(Some(index), None)?,
+            Token::Sheet(name) | Token::SheetQuoted(name) => {
+                self.parse_qualified(None, Some(SheetRef::Single(name)))?
+            }
+            Token::MultipleSheets(first, last) | Token::MultipleSheetsQuoted(first, last) => {
+                self.parse_qualified(None, Some(SheetRef::Range(first, last)))?
+            }
+            Token::ExcelFunction(name) => self.parse_call(name, CallKind::Builtin, token.span)?,
+            Token::RefFunction(name) => self.parse_call(name, CallKind::Reference, token.span)?,
+            Token::RefFunctionCond(name) => {
+                self.parse_call(name, CallKind::ConditionalReference, token.span)?
+            }
+            Token::Udf(name) => self.parse_call(name, CallKind::UserDefined, token.span)?,
+            Token::LeftParen => self.parse_group()?,
+            Token::LeftBrace => self.parse_array()?,
+            Token::DdeCall {
+                application,
+                topic,
+                item,
+            } => Expr::Reference(Reference::local(ReferenceKind::Dde {
+                application,
+                topic,
+                item,
+            })),
+            value => match reference_kind(value) {
+                Ok(kind) => Expr::Reference(Reference::local(kind)),
+                Err(value) => {
+                    return Err(Self::unexpected(Spanned {
+                        value,
+                        span: token.span,
+                    }));
+                }
+            },
+        };
+        Ok(expr)
+    }
+
+    /// Parses the rest of `[1]Sheet1!A1`, `Sheet1!A1` or `[1]!Name` after the
+    /// workbook or sheet prefix token.
+    fn parse_qualified(
+        &mut self,
+        workbook: Option<u32>,
+        mut sheet: Option<SheetRef>,
+    ) -> Result<Expr, ParseError> {
+        if workbook.is_some() && sheet.is_none() {
+            match self.current().value.clone() {
+                Token::Sheet(name) | Token::SheetQuoted(name) => {
+                    self.advance();
+                    sheet = Some(SheetRef::Single(name));
+                }
+                Token::MultipleSheets(first, last) | Token::MultipleSheetsQuoted(first, last) => {
+                    self.advance();
+                    sheet = Some(SheetRef::Range(first, last));
+                }
+                _ => {}
+            }
+        }
+        self.expect(Token::Exclamation)?;
+
+        let token = self.advance();
+        if token.value == Token::ErrorRef {
+            return Ok(Expr::Literal(Literal::Error("#REF!".to_string())));
+        }
+        if matches!(token.value, Token::StructuredRef(_)) {
+            // Table names are workbook-global and never sheet-qualified
+            return Err(Self::unexpected(token));
+        }
+        match reference_kind(token.value) {
+            Ok(kind) => Ok(Expr::Reference(Reference {
+                workbook,
+                sheet,
+                kind,
+            })),
+            Err(value) => Err(Self::unexpected(Spanned {
+                value,
+                span: token.span,
+            })),
+        }
+    }
+
+    /// Parses call arguments after the function token, which already
+    /// includes the opening parenthesis. Empty arguments become
+    /// [`Expr::Missing`]. Calls to functions in the [`functions`] catalog
+    /// must pass an argument count it allows, and references where it
+    /// asks for them.
+    fn parse_call(
+        &mut self,
+        name: String,
+        kind: CallKind,
+        start: Span,
+    ) -> Result<Expr, ParseError> {
+        let mut args = Vec::new();
+        let end = if matches!(self.current().value, Token::RightParen) {
+            self.advance().span
+        } else {
+            loop {
+                let arg = match self.current().value {
+                    Token::Comma | Token::RightParen => Expr::Missing,
+                    _ => self.parse_expr(0)?,
+                };
+                args.push(arg);
+
+                let token = self.advance();
+                match token.value {
+                    Token::Comma => continue,
+                    Token::RightParen => break token.span,
+                    _ => return Err(Self::unexpected(token)),
+                }
+            }
+        };
+
+        let function = match kind {
+            CallKind::UserDefined => None,
+            _ => functions::lookup(&name),
+        };
+        if let Some(function) = function {
+            let span = Span {
+                end: end.end,
+                char_end: end.char_end,
+                ..start
+            };
+            if !function.accepts(args.len()) {
+                return Err(ParseError::WrongArgumentCount {
+                    name,
+                    min: function.min_args,
+                    max: function.max_args,
+                    repeat: function.repeat,
+                    found: args.len(),
+                    span,
+                });
+            }
+            let constant = args.iter().enumerate().position(|(index, arg)| {
+                function.arg(index) == Some(Arg::Reference) && !arg.is_reference()
+            });
+            if let Some(index) = constant {
+                return Err(ParseError::ExpectedReference {
+                    name,
+                    position: index + 1,
+                    span,
+                });
+            }
+        }
+        Ok(Expr::Call { name, kind, args })
+    }
+
+    /// Parses a parenthesized expression or a union `(A1,B2)`
+    fn parse_group(&mut self) -> Result<Expr, ParseError> {
+        let mut items = vec![self.parse_expr(0)?];
+        while matches!(self.current().value, Token::Comma) {
+            self.advance();
+            items.push(self.parse_expr(0)?);
+        }
+        self.expect(Token::RightParen)?;
+
+        if items.len() == 1 {
+            Ok(items.remove(0))
+        } else {
+            Ok(Expr::Union(items))
+        }
+    }
+
+    /// Parses `{1,2;3,4}` into rows of constants, all of the same length
+    fn parse_array(&mut self) -> Result<Expr, ParseError> {
+        let mut rows = vec![Vec::new()];
+        let mut row_start = self.current().span;
+        loop {
+            let element = self.parse_array_element()?;
+            rows.last_mut().unwrap().push(element);
+
+            let token = self.advance();
+            let row_end = token.span;
+            match token.value {
+                Token::Comma => continue,
+                Token::Semicolon | Token::RightBrace => {}
+                _ => return Err(Self::unexpected(token)),
+            }
+
+            let expected = rows[0].len();
+            let found = rows.last().unwrap().len();
+            if found != expected {
+                return Err(ParseError::RaggedArray {
+                    row: rows.len(),
+                    expected,
+                    found,
+                    span: Span {
+                        end: row_end.start,
+                        char_end: row_end.char_start,
+                        ..row_start
+                    },
+                });
+            }
+            if token.value == Token::RightBrace {
+                break;
+            }
+            rows.push(Vec::new());
+            row_start = self.current().span;
+        }
+        Ok(Expr::Array(rows))
+    }
+
+    /// Array elements are constants only: numbers (optionally signed),
+    /// strings, booleans and errors
+    fn parse_array_element(&mut self) -> Result<Expr, ParseError> {
+        let token = self.advance();
+        let literal = match token.value {
+            Token::Number(n) => Literal::Number(n),
+            Token::String(s) => Literal::String(s),
+            Token::Bool(b) => Literal::Bool(b),
+            Token::Error(e) => Literal::Error(e),
+            Token::ErrorRef => Literal::Error("#REF!".to_string()),
+            Token::Plus | Token::Minus => {
+                let sign = if token.value == Token::Minus {
+                    -1.0
+                } else {
+                    1.0
+                };
+                let number = self.advance();
+                match number.value {
+                    Token::Number(n) => Literal::Number(sign * n),
+                    _ => return Err(Self::unexpected(number)),
+                }
+            }
+            _ => return Err(Self::unexpected(token)),
+        };
+        Ok(Expr::Literal(literal))
+    }
+}
+
+/// Builds `left:right`, carrying a sheet prefix on the left over to an
+/// unqualified right end so `Sheet1!A1:B2` reads as one sheet's range.
+fn range(left: Expr, mut right: Expr) -> Expr {
+    if let (Expr::Reference(start), Expr::Reference(end)) = (&left, &mut right)
+        && end.sheet.is_none()
+        && end.workbook.is_none()
+    {
+        end.workbook = start.workbook;
+        end.sheet = start.sheet.clone();
+    }
+    Expr::Range(Box::new(left), Box::new(right))
+}
+
+/// Converts a single-token reference, handing back any other token
+fn reference_kind(token: Token) -> Result<ReferenceKind, Token> {
+    match token {
+        Token::Cell(cell) => Ok(ReferenceKind::Cell(cell)),
+        Token::HorizontalRange(first, last) => Ok(ReferenceKind::Rows(first, last)),
+        Token::VerticalRange(first, last) => Ok(ReferenceKind::Columns(first, last)),
+        Token::NamedRange(name) => Ok(ReferenceKind::Name(name)),
+        Token::ReservedName(name) => Ok(ReferenceKind::ReservedName(name)),
+        Token::StructuredRef(reference) => Ok(ReferenceKind::Structured(reference)),
+        other => Err(other),
+    }
+}
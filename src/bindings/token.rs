@@ -1,9 +1,250 @@
+/// Location of a token or error in the source formula
+///
+/// Offsets are half-open (`start..end`) and given both in bytes, for slicing
+/// the original `&str`, and in chars, for editors that count characters.
+/// `line` and `column` are 1-based and locate the start of the span.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub char_start: usize,
+    pub char_end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A value together with its location in the source formula
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned<T> {
+    pub value: T,
+    pub span: Span,
+}
+
 /// Lexer error types
 #[derive(Debug, Clone, PartialEq)]
 pub enum LexerError {
-    UnexpectedChar(char),
-    UnterminatedString,
-    InvalidNumber(String),
+    UnexpectedChar(char, Span),
+    UnterminatedString(Span),
+    InvalidNumber(String, Span),
+    MissingExponentDigits(String, Span),
+    InvalidCellReference(String, Span),
+    InvalidRange(String, Span),
+    UnterminatedSheetName(Span),
+    UnterminatedStructuredReference(Span),
+    InvalidStructuredReference(String, Span),
+    UnterminatedDdeCall(Span),
+}
+
+impl LexerError {
+    /// Location of the offending input
+    pub fn span(&self) -> Span {
+        match self {
+            Self::UnexpectedChar(_, span)
+            | Self::UnterminatedString(span)
+            | Self::InvalidNumber(_, span)
+            | Self::MissingExponentDigits(_, span)
+            | Self::InvalidCellReference(_, span)
+            | Self::InvalidRange(_, span)
+            | Self::UnterminatedSheetName(span)
+            | Self::UnterminatedStructuredReference(span)
+            | Self::InvalidStructuredReference(_, span)
+            | Self::UnterminatedDdeCall(span) => *span,
+        }
+    }
+}
+
+impl std::fmt::Display for LexerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedChar(c, _) => write!(f, "unexpected character '{c}'"),
+            Self::UnterminatedString(_) => write!(f, "unterminated string"),
+            Self::InvalidNumber(text, _) => write!(f, "invalid number '{text}'"),
+            Self::MissingExponentDigits(text, _) => {
+                write!(f, "exponent of '{text}' is missing digits")
+            }
+            Self::InvalidCellReference(text, _) => write!(f, "invalid cell reference '{text}'"),
+            Self::InvalidRange(text, _) => write!(f, "invalid range '{text}'"),
+            Self::UnterminatedSheetName(_) => write!(f, "unterminated sheet name"),
+            Self::UnterminatedStructuredReference(_) => {
+                write!(f, "unterminated structured reference")
+            }
+            Self::InvalidStructuredReference(text, _) => {
+                write!(f, "invalid structured reference '{text}'")
+            }
+            Self::UnterminatedDdeCall(_) => write!(f, "unterminated DDE call"),
+        }
+    }
+}
+
+impl std::error::Error for LexerError {}
+
+/// Largest column index Excel accepts (`XFD`)
+pub const MAX_COLUMN: u32 = 16_384;
+
+/// Largest row number Excel accepts
+pub const MAX_ROW: u32 = 1_048_576;
+
+/// A single cell reference such as `A1`, `$A1`, `A$1` or `$A$1`
+///
+/// Columns and rows are 1-based, so `B3` has `column == 2` and `row == 3`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellRef {
+    pub column: u32,
+    pub row: u32,
+    pub absolute_column: bool,
+    pub absolute_row: bool,
+}
+
+impl CellRef {
+    /// Relative reference to the given 1-based column and row
+    pub fn new(column: u32, row: u32) -> Self {
+        Self {
+            column,
+            row,
+            absolute_column: false,
+            absolute_row: false,
+        }
+    }
+}
+
+impl std::fmt::Display for CellRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.absolute_column {
+            write!(f, "$")?;
+        }
+        write!(f, "{}", column_name(self.column))?;
+        if self.absolute_row {
+            write!(f, "$")?;
+        }
+        write!(f, "{}", self.row)
+    }
+}
+
+/// One end of a whole-row (`1:5`) or whole-column (`A:C`) range
+///
+/// `index` is the 1-based row or column number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeBound {
+    pub index: u32,
+    pub absolute: bool,
+}
+
+/// Special item specifier inside a structured reference
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableItem {
+    All,     // [#All]
+    Data,    // [#Data]
+    Headers, // [#Headers]
+    Totals,  // [#Totals]
+    ThisRow, // [#This Row] or the @ shorthand
+}
+
+impl TableItem {
+    /// Parses the text after `#`, e.g. `This Row` (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_uppercase().as_str() {
+            "ALL" => Some(Self::All),
+            "DATA" => Some(Self::Data),
+            "HEADERS" => Some(Self::Headers),
+            "TOTALS" => Some(Self::Totals),
+            "THIS ROW" => Some(Self::ThisRow),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::All => "#All",
+            Self::Data => "#Data",
+            Self::Headers => "#Headers",
+            Self::Totals => "#Totals",
+            Self::ThisRow => "#This Row",
+        }
+    }
+}
+
+/// A structured table reference such as `Sales[Amount]`, `[@Qty]` or
+/// `Sales[[#Headers],[Q1]:[Q4]]`
+///
+/// `table` is `None` for references written inside the table itself.
+/// Column names are stored unescaped.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StructuredRef {
+    pub table: Option<String>,
+    pub items: Vec<TableItem>,
+    pub column: Option<String>,
+    pub last_column: Option<String>,
+}
+
+impl std::fmt::Display for StructuredRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let escape = |name: &str| {
+            name.chars().fold(String::new(), |mut out, c| {
+                if "[]#'".contains(c) {
+                    out.push('\'');
+                }
+                out.push(c);
+                out
+            })
+        };
+
+        if let Some(table) = &self.table {
+            write!(f, "{table}")?;
+        }
+        match (self.items.as_slice(), &self.column, &self.last_column) {
+            ([], None, _) => write!(f, "[]"),
+            ([], Some(column), None) => write!(f, "[{}]", escape(column)),
+            ([TableItem::ThisRow], Some(column), None) => write!(f, "[@[{}]]", escape(column)),
+            ([item], None, _) => write!(f, "[{}]", item.name()),
+            (items, column, last_column) => {
+                let mut parts: Vec<String> = items
+                    .iter()
+                    .map(|item| format!("[{}]", item.name()))
+                    .collect();
+                if let Some(column) = column {
+                    let mut part = format!("[{}]", escape(column));
+                    if let Some(last) = last_column {
+                        part.push_str(&format!(":[{}]", escape(last)));
+                    }
+                    parts.push(part);
+                }
+                write!(f, "[{}]", parts.join(","))
+            }
+        }
+    }
+}
+
+/// Converts column letters (`A`, `AA`, `XFD`) into a 1-based column index.
+///
+/// Letters are case-insensitive. Returns `None` for empty input, non-letters,
+/// or columns beyond [`MAX_COLUMN`].
+pub fn column_index(letters: &str) -> Option<u32> {
+    if letters.is_empty() {
+        return None;
+    }
+    let mut index: u32 = 0;
+    for c in letters.chars() {
+        if !c.is_ascii_alphabetic() {
+            return None;
+        }
+        let digit = c.to_ascii_uppercase() as u32 - 'A' as u32 + 1;
+        index = index.checked_mul(26)?.checked_add(digit)?;
+        if index > MAX_COLUMN {
+            return None;
+        }
+    }
+    Some(index)
+}
+
+/// Converts a 1-based column index back into its letters (`28` -> `AB`)
+pub fn column_name(mut index: u32) -> String {
+    let mut letters = Vec::new();
+    while index > 0 {
+        let rem = (index - 1) % 26;
+        letters.push((b'A' + rem as u8) as char);
+        index = (index - 1) / 26;
+    }
+    letters.iter().rev().collect()
 }
 
 /// Token types for Excel formulas
@@ -16,6 +257,38 @@ pub enum Token {
     Error(String),
     ErrorRef, // #REF! - separate from other errors per spec
 
+    // References
+    Cell(CellRef),
+    HorizontalRange(RangeBound, RangeBound), // 1:5, $3:$7
+    VerticalRange(RangeBound, RangeBound),   // A:C, $A:$Z
+
+    // Names
+    NamedRange(String),   // TaxRate, TRUE_VALUE, A1B
+    ReservedName(String), // _xlnm.Print_Area, stored without the prefix
+
+    // Structured references
+    StructuredRef(StructuredRef), // Sales[Amount], [@Qty], Sales[[#Totals],[Q1]:[Q4]]
+
+    // Sheet prefixes, always followed by Exclamation
+    Sheet(String),                        // Sheet2
+    SheetQuoted(String),                  // 'Q1 Budget'
+    MultipleSheets(String, String),       // Jan:Dec
+    MultipleSheetsQuoted(String, String), // 'Jan 2020:Dec 2020'
+
+    // External references
+    File(u32), // [1], index into the workbook's external links
+    DdeCall {
+        application: String,
+        topic: String,
+        item: Option<String>,
+    }, // MSEXCEL|'Sheet1'!R1C1
+
+    // Function calls, each including the opening parenthesis
+    ExcelFunction(String),   // SUM(
+    RefFunction(String),     // INDEX(, OFFSET(, INDIRECT(
+    RefFunctionCond(String), // IF(, CHOOSE(
+    Udf(String),             // MyMacro(, _xll.Foo(
+
     // Operators - Arithmetic
     Plus,
     Minus,
@@ -23,6 +296,9 @@ pub enum Token {
     Divide,
     Power,
 
+    // Operators - Postfix
+    Percent,
+
     // Operators - Comparison
     Equal,
     NotEqual,
@@ -34,12 +310,103 @@ pub enum Token {
     // Operators - String
     Concatenate,
 
+    // Operators - Reference
+    Intersect, // whitespace between two references
+
     // Delimiters
+    LeftParen,
+    RightParen,
     LeftBrace,
     RightBrace,
     Comma,
     Semicolon,
+    Colon,
+    Exclamation,
 
     // End of input
     Eof,
 }
+
+/// Renders the token as it would appear in a formula
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bound = |bound: &RangeBound, text: String| {
+            if bound.absolute {
+                format!("${text}")
+            } else {
+                text
+            }
+        };
+        match self {
+            Self::Number(n) => write!(f, "{n}"),
+            Self::String(s) => write!(f, "\"{}\"", s.replace('"', "\"\"")),
+            Self::Bool(b) => write!(f, "{}", if *b { "TRUE" } else { "FALSE" }),
+            Self::Error(e) => write!(f, "{e}"),
+            Self::ErrorRef => write!(f, "#REF!"),
+            Self::Cell(cell) => write!(f, "{cell}"),
+            Self::HorizontalRange(first, last) => write!(
+                f,
+                "{}:{}",
+                bound(first, first.index.to_string()),
+                bound(last, last.index.to_string())
+            ),
+            Self::VerticalRange(first, last) => write!(
+                f,
+                "{}:{}",
+                bound(first, column_name(first.index)),
+                bound(last, column_name(last.index))
+            ),
+            Self::NamedRange(name) => write!(f, "{name}"),
+            Self::ReservedName(name) => write!(f, "_xlnm.{name}"),
+            Self::StructuredRef(reference) => write!(f, "{reference}"),
+            Self::Sheet(name) => write!(f, "{name}"),
+            Self::SheetQuoted(name) => write!(f, "'{}'", name.replace('\'', "''")),
+            Self::MultipleSheets(first, last) => write!(f, "{first}:{last}"),
+            Self::MultipleSheetsQuoted(first, last) => write!(
+                f,
+                "'{}:{}'",
+                first.replace('\'', "''"),
+                last.replace('\'', "''")
+            ),
+            Self::File(index) => write!(f, "[{index}]"),
+            Self::DdeCall {
+                application,
+                topic,
+                item,
+            } => {
+                write!(f, "{application}|'{}'", topic.replace('\'', "''"))?;
+                match item {
+                    Some(item) => write!(f, "!{item}"),
+                    None => Ok(()),
+                }
+            }
+            Self::ExcelFunction(name)
+            | Self::RefFunction(name)
+            | Self::RefFunctionCond(name)
+            | Self::Udf(name) => write!(f, "{name}("),
+            Self::Plus => write!(f, "+"),
+            Self::Minus => write!(f, "-"),
+            Self::Multiply => write!(f, "*"),
+            Self::Divide => write!(f, "/"),
+            Self::Power => write!(f, "^"),
+            Self::Percent => write!(f, "%"),
+            Self::Equal => write!(f, "="),
+            Self::NotEqual => write!(f, "<>"),
+            Self::Less => write!(f, "<"),
+            Self::Greater => write!(f, ">"),
+            Self::LessEqual => write!(f, "<="),
+            Self::GreaterEqual => write!(f, ">="),
+            Self::Concatenate => write!(f, "&"),
+            Self::Intersect => write!(f, " "),
+            Self::LeftParen => write!(f, "("),
+            Self::RightParen => write!(f, ")"),
+            Self::LeftBrace => write!(f, "{{"),
+            Self::RightBrace => write!(f, "}}"),
+            Self::Comma => write!(f, ","),
+            Self::Semicolon => write!(f, ";"),
+            Self::Colon => write!(f, ":"),
+            Self::Exclamation => write!(f, "!"),
+            Self::Eof => write!(f, "end of formula"),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+pub mod python;
+pub mod runtime;
+
+use std::fmt;
+
+use wasm_bindgen::prelude::*;
+
+/// How generated code reads a cell
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReferenceStyle {
+    /// `xl("A1")`, `xl("Sheet2!B3")`: a caller-supplied accessor function
+    #[default]
+    Function,
+    /// `cells["Sheet1"]["A1"]`: nested dicts keyed by sheet, then cell
+    Dict,
+    /// `ws["A1"].value`, `wb["Sheet2"]["B3"].value`: openpyxl worksheets
+    Openpyxl,
+    /// `df.iat[0, 0]`, `dfs["Sheet2"].iat[2, 1]`: pandas DataFrames
+    Pandas,
+}
+
+/// What ranges such as `A1:B10` become in generated code
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// Nested lists, one list per row
+    #[default]
+    Python,
+    /// `np.ndarray` values, aggregated with numpy functions
+    Numpy,
+    /// `pd.DataFrame` values, sliced with `.iloc` where possible
+    Pandas,
+}
+
+/// Settings for a single transpile call
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranspileOptions {
+    /// Inline the source of the helpers the formula uses instead of
+    /// importing them from `expy_runtime`, so the output runs on its own
+    #[wasm_bindgen(js_name = inlineHelpers)]
+    pub inline_helpers: bool,
+    #[wasm_bindgen(js_name = referenceStyle)]
+    pub reference_style: ReferenceStyle,
+    pub backend: Backend,
+    /// Sheet the formula lives on, for styles that always name the sheet
+    #[wasm_bindgen(getter_with_clone)]
+    pub sheet: String,
+}
+
+impl Default for TranspileOptions {
+    fn default() -> Self {
+        Self {
+            inline_helpers: false,
+            reference_style: ReferenceStyle::default(),
+            backend: Backend::default(),
+            sheet: "Sheet1".to_string(),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl TranspileOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Code generation error types
+#[derive(Debug, Clone, PartialEq)]
+pub enum CodegenError {
+    /// A construct that parses but has no translation yet
+    Unsupported(String),
+}
+
+impl fmt::Display for CodegenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsupported(what) => write!(f, "{what} is not supported yet"),
+        }
+    }
+}
+
+impl std::error::Error for CodegenError {}
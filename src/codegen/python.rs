@@ -0,0 +1,913 @@
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+
+use crate::bindings::ast::{BinaryOp, Expr, Literal, Reference, ReferenceKind, SheetRef, UnaryOp};
+use crate::bindings::token::{CellRef, MAX_COLUMN, MAX_ROW, column_name};
+use crate::criteria::{Criterion, Operand};
+use crate::format::{FormatError, NumberFormat, Part, Section};
+use crate::functions::{self, Arg, ReturnType, Translation};
+
+use super::runtime;
+use super::{Backend, CodegenError, ReferenceStyle, TranspileOptions};
+
+// Python operator precedence, loosest first. Only the levels the emitter
+// produces are listed.
+const CONDITIONAL: u8 = 0;
+const COMPARISON: u8 = 1;
+const ADDITIVE: u8 = 2;
+const MULTIPLICATIVE: u8 = 3;
+const UNARY: u8 = 4;
+const POWER: u8 = 5;
+const ATOM: u8 = 6;
+
+/// What the emitter knows statically about an expression's value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Number,
+    Text,
+    Bool,
+    Range,
+    Any,
+}
+
+/// An emitted Python expression with its precedence and value kind
+struct Code {
+    text: String,
+    precedence: u8,
+    kind: Kind,
+}
+
+impl Code {
+    fn new(text: String, precedence: u8, kind: Kind) -> Self {
+        Self {
+            text,
+            precedence,
+            kind,
+        }
+    }
+
+    /// The expression text, parenthesized unless it binds at least as
+    /// tightly as `precedence`
+    fn at(&self, precedence: u8) -> String {
+        if self.precedence >= precedence {
+            self.text.clone()
+        } else {
+            format!("({})", self.text)
+        }
+    }
+}
+
+/// A block of cells on one sheet. Bounds are 1-based and inclusive; `None`
+/// spans every row (or column), as in `A:C`.
+struct Area<'a> {
+    reference: &'a Reference,
+    text: String,
+    rows: Option<(u32, u32)>,
+    columns: Option<(u32, u32)>,
+}
+
+/// Translates a parsed formula into a single Python expression.
+///
+/// References are read through an `xl()` accessor supplied by the caller,
+/// e.g. `Sheet2!B3` becomes `xl("Sheet2!B3")`. Operators map onto Python's
+/// own where the semantics agree; comparisons involving text and `&` go
+/// through the helpers in [`runtime`], which are collected as they are used.
+#[derive(Debug, Default)]
+pub struct PythonEmitter {
+    options: TranspileOptions,
+    imports: BTreeSet<&'static str>,
+    helpers: BTreeSet<&'static str>,
+}
+
+impl PythonEmitter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_options(options: TranspileOptions) -> Self {
+        Self {
+            options,
+            imports: BTreeSet::new(),
+            helpers: BTreeSet::new(),
+        }
+    }
+
+    /// Emits the expression alone; see [`Self::helpers`] for what it needs
+    pub fn emit(&mut self, expr: &Expr) -> Result<String, CodegenError> {
+        Ok(self.emit_code(expr)?.text)
+    }
+
+    /// Emits the expression preceded by what it needs to run: library
+    /// imports plus an import from the runtime module, or the helpers'
+    /// source when inlining
+    pub fn emit_program(&mut self, expr: &Expr) -> Result<String, CodegenError> {
+        let body = self.emit(expr)?;
+        if self.imports.is_empty() && self.helpers.is_empty() {
+            return Ok(body);
+        }
+        let helpers = runtime::resolve(self.helpers.iter().copied());
+        if self.options.inline_helpers {
+            self.imports
+                .extend(runtime::imports(helpers.iter().copied()));
+        }
+
+        let mut program = String::new();
+        for import in &self.imports {
+            program.push_str(import);
+            program.push('\n');
+        }
+        if self.helpers.is_empty() {
+            program.push('\n');
+        } else if self.options.inline_helpers {
+            if !self.imports.is_empty() {
+                program.push('\n');
+            }
+            for helper in helpers {
+                program.push_str(helper.source);
+                program.push_str("\n\n\n");
+            }
+        } else {
+            let names: Vec<&str> = self.helpers.iter().copied().collect();
+            program.push_str(&format!(
+                "from {} import {}\n\n",
+                runtime::MODULE_NAME,
+                names.join(", ")
+            ));
+        }
+        program.push_str(&body);
+        Ok(program)
+    }
+
+    /// Names of the runtime helpers used so far, without dependencies
+    pub fn helpers(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.helpers.iter().copied()
+    }
+
+    fn call(&mut self, helper: &'static str, args: &[String], kind: Kind) -> Code {
+        self.helpers.insert(helper);
+        Code::new(format!("{helper}({})", args.join(", ")), ATOM, kind)
+    }
+
+    fn emit_code(&mut self, expr: &Expr) -> Result<Code, CodegenError> {
+        match expr {
+            Expr::Literal(literal) => Ok(self.emit_literal(literal)),
+            Expr::Reference(reference) => self.emit_reference(reference),
+            Expr::Range(left, right) => self.emit_range(expr, left, right),
+            Expr::Intersect(..) => Err(CodegenError::Unsupported("intersection".into())),
+            Expr::Union(_) => Err(CodegenError::Unsupported("union".into())),
+            Expr::Unary { op, operand } => self.emit_unary(*op, operand),
+            Expr::Percent(operand) => {
+                let operand = self.emit_code(operand)?;
+                let operand = self.number_operand(operand)?;
+                let text = format!("{} / 100", operand.at(MULTIPLICATIVE));
+                Ok(Code::new(text, MULTIPLICATIVE, operand.kind))
+            }
+            Expr::Binary { op, left, right } => self.emit_binary(*op, left, right),
+            Expr::Call { name, args, .. } => self.emit_call(name, args),
+            Expr::Array(rows) => {
+                let mut emitted = Vec::with_capacity(rows.len());
+                for row in rows {
+                    let values = row
+                        .iter()
+                        .map(|value| Ok(self.emit_code(value)?.text))
+                        .collect::<Result<Vec<_>, CodegenError>>()?;
+                    emitted.push(format!("[{}]", values.join(", ")));
+                }
+                Ok(self.wrap_values(format!("[{}]", emitted.join(", "))))
+            }
+            // An empty argument, as in IF(A1,,1), passes 0
+            Expr::Missing => Ok(Code::new("0".to_string(), ATOM, Kind::Number)),
+        }
+    }
+
+    fn emit_literal(&mut self, literal: &Literal) -> Code {
+        match literal {
+            Literal::Number(n) => Code::new(number(*n), ATOM, Kind::Number),
+            Literal::String(s) => Code::new(string(s), ATOM, Kind::Text),
+            Literal::Bool(b) => {
+                let text = if *b { "True" } else { "False" };
+                Code::new(text.to_string(), ATOM, Kind::Bool)
+            }
+            Literal::Error(e) => self.call("ExcelError", &[string(e)], Kind::Any),
+        }
+    }
+
+    fn emit_reference(&mut self, reference: &Reference) -> Result<Code, CodegenError> {
+        match reference.kind {
+            ReferenceKind::Rows(first, last) => {
+                return self.emit_area(Area {
+                    reference,
+                    text: reference.to_string(),
+                    rows: Some((first.index, last.index)),
+                    columns: None,
+                });
+            }
+            ReferenceKind::Columns(first, last) => {
+                return self.emit_area(Area {
+                    reference,
+                    text: reference.to_string(),
+                    rows: None,
+                    columns: Some((first.index, last.index)),
+                });
+            }
+            _ => {}
+        }
+
+        let style = self.options.reference_style;
+        if style == ReferenceStyle::Function {
+            let text = format!("xl({})", string(&reference.to_string()));
+            return Ok(Code::new(text, ATOM, Kind::Any));
+        }
+
+        let sheet = self.sheet_of(reference, &reference.to_string())?;
+        let text = match (&reference.kind, style) {
+            (ReferenceKind::Name(name), ReferenceStyle::Dict) if sheet.is_none() => {
+                format!("names[{}]", string(name))
+            }
+            (ReferenceKind::Cell(cell), ReferenceStyle::Dict) => {
+                let sheet = sheet.unwrap_or(&self.options.sheet);
+                let cell = format!("{}{}", column_name(cell.column), cell.row);
+                format!("cells[{}][{}]", string(sheet), string(&cell))
+            }
+            (ReferenceKind::Cell(cell), ReferenceStyle::Openpyxl) => {
+                let cell = string(&format!("{}{}", column_name(cell.column), cell.row));
+                match sheet {
+                    Some(sheet) => format!("wb[{}][{cell}].value", string(sheet)),
+                    None => format!("ws[{cell}].value"),
+                }
+            }
+            (ReferenceKind::Cell(cell), ReferenceStyle::Pandas) => {
+                let position = format!("{}, {}", cell.row - 1, cell.column - 1);
+                match sheet {
+                    Some(sheet) => format!("dfs[{}].iat[{position}]", string(sheet)),
+                    None => format!("df.iat[{position}]"),
+                }
+            }
+            _ => return Err(self.unsupported_reference(&reference.to_string())),
+        };
+        Ok(Code::new(text, ATOM, Kind::Any))
+    }
+
+    fn unsupported_reference(&self, text: &str) -> CodegenError {
+        let style = self.options.reference_style;
+        CodegenError::Unsupported(format!("{text} with the {style:?} reference style"))
+    }
+
+    /// The sheet a reference names explicitly, for styles other than
+    /// [`ReferenceStyle::Function`], which cannot reach other workbooks or
+    /// span several sheets
+    fn sheet_of<'a>(
+        &self,
+        reference: &'a Reference,
+        text: &str,
+    ) -> Result<Option<&'a str>, CodegenError> {
+        match (&reference.workbook, &reference.sheet) {
+            (None, None) => Ok(None),
+            (None, Some(SheetRef::Single(sheet))) => Ok(Some(sheet.as_str())),
+            _ => Err(self.unsupported_reference(text)),
+        }
+    }
+
+    /// `A1:B10` between two cells on the same sheet
+    fn emit_range(&mut self, expr: &Expr, left: &Expr, right: &Expr) -> Result<Code, CodegenError> {
+        if let (Some(start), Some(end)) = (range_end(left), range_end(right))
+            && let (ReferenceKind::Cell(first), ReferenceKind::Cell(last)) =
+                (&start.kind, &end.kind)
+            && start.workbook == end.workbook
+            && start.sheet == end.sheet
+        {
+            return self.emit_area(Area {
+                reference: &start,
+                text: format!("{start}:{}", Reference::local(end.kind.clone())),
+                rows: Some((first.row.min(last.row), first.row.max(last.row))),
+                columns: Some((first.column.min(last.column), first.column.max(last.column))),
+            });
+        }
+        Err(CodegenError::Unsupported(format!("range {expr}")))
+    }
+
+    /// A block of cells as a 2D value of the configured backend
+    fn emit_area(&mut self, area: Area) -> Result<Code, CodegenError> {
+        let style = self.options.reference_style;
+        if style == ReferenceStyle::Function {
+            let values = format!("xl({})", string(&area.text));
+            return Ok(self.wrap_values(values));
+        }
+
+        let sheet = self.sheet_of(area.reference, &area.text)?;
+        if style == ReferenceStyle::Pandas {
+            let frame = match sheet {
+                Some(sheet) => format!("dfs[{}]", string(sheet)),
+                None => "df".to_string(),
+            };
+            let slice = |bounds: Option<(u32, u32)>| match bounds {
+                Some((first, last)) => format!("{}:{last}", first - 1),
+                None => ":".to_string(),
+            };
+            let frame = format!(
+                "{frame}.iloc[{}, {}]",
+                slice(area.rows),
+                slice(area.columns)
+            );
+            let text = match self.options.backend {
+                Backend::Python => format!("{frame}.values.tolist()"),
+                Backend::Numpy => format!("{frame}.to_numpy()"),
+                Backend::Pandas => frame,
+            };
+            return Ok(Code::new(text, ATOM, Kind::Range));
+        }
+
+        // Dict and openpyxl lookups need every cell named explicitly
+        let (Some((first_row, last_row)), Some((first_column, last_column))) =
+            (area.rows, area.columns)
+        else {
+            return Err(self.unsupported_reference(&area.text));
+        };
+        let values = match style {
+            ReferenceStyle::Dict => {
+                let sheet = string(sheet.unwrap_or(&self.options.sheet));
+                let columns: Vec<String> = (first_column..=last_column)
+                    .map(|column| string(&column_name(column)))
+                    .collect();
+                format!(
+                    "[[cells[{sheet}][column + str(row)] for column in [{}]] for row in range({first_row}, {})]",
+                    columns.join(", "),
+                    last_row + 1
+                )
+            }
+            _ => {
+                let block = format!(
+                    "{}{first_row}:{}{last_row}",
+                    column_name(first_column),
+                    column_name(last_column)
+                );
+                let worksheet = match sheet {
+                    Some(sheet) => format!("wb[{}]", string(sheet)),
+                    None => "ws".to_string(),
+                };
+                format!(
+                    "[[cell.value for cell in row] for row in {worksheet}[{}]]",
+                    string(&block)
+                )
+            }
+        };
+        Ok(self.wrap_values(values))
+    }
+
+    /// Converts nested lists into the backend's 2D type
+    fn wrap_values(&mut self, values: String) -> Code {
+        let text = match self.options.backend {
+            Backend::Python => values,
+            Backend::Numpy => {
+                self.imports.insert("import numpy as np");
+                format!("np.array({values})")
+            }
+            Backend::Pandas => {
+                self.imports.insert("import pandas as pd");
+                format!("pd.DataFrame({values})")
+            }
+        };
+        Code::new(text, ATOM, Kind::Range)
+    }
+
+    fn emit_unary(&mut self, op: UnaryOp, operand: &Expr) -> Result<Code, CodegenError> {
+        let operand = self.emit_code(operand)?;
+        match op {
+            // Excel's unary plus leaves its operand untouched
+            UnaryOp::Plus => Ok(operand),
+            UnaryOp::Negate => {
+                let operand = self.number_operand(operand)?;
+                let text = format!("-{}", operand.at(UNARY));
+                Ok(Code::new(text, UNARY, operand.kind))
+            }
+        }
+    }
+
+    fn emit_binary(
+        &mut self,
+        op: BinaryOp,
+        left: &Expr,
+        right: &Expr,
+    ) -> Result<Code, CodegenError> {
+        if op == BinaryOp::Concat {
+            return self.emit_concat(left, right);
+        }
+
+        // Division and powers stay native only when the right operand is a
+        // literal that cannot trigger #DIV/0! or #NUM!; the helpers coerce
+        // their operands themselves
+        let literal = literal_number(right);
+        let left = self.emit_code(left)?;
+        let right = self.emit_code(right)?;
+
+        match op {
+            BinaryOp::Add => self.arithmetic(left, "+", right, ADDITIVE),
+            BinaryOp::Subtract => self.arithmetic(left, "-", right, ADDITIVE),
+            BinaryOp::Multiply => self.arithmetic(left, "*", right, MULTIPLICATIVE),
+            BinaryOp::Divide if literal.is_some_and(|n| n != 0.0) => {
+                self.arithmetic(left, "/", right, MULTIPLICATIVE)
+            }
+            BinaryOp::Divide => self.scalar_arithmetic("divide", left, right),
+            BinaryOp::Power if literal.is_some_and(|n| n > 0.0 && n.fract() == 0.0) => {
+                // Python's ** is right-associative and binds tighter than
+                // unary minus, so the base needs parentheses unless atomic
+                let left = self.number_operand(left)?;
+                let text = format!("{} ** {}", left.at(ATOM), right.at(UNARY));
+                Ok(Code::new(text, POWER, left.kind))
+            }
+            BinaryOp::Power => self.scalar_arithmetic("power", left, right),
+            BinaryOp::Concat => unreachable!(),
+            BinaryOp::Equal
+            | BinaryOp::NotEqual
+            | BinaryOp::Less
+            | BinaryOp::Greater
+            | BinaryOp::LessEqual
+            | BinaryOp::GreaterEqual => Ok(self.emit_comparison(op, left, right)),
+        }
+    }
+
+    /// A native Python operator between two coerced operands
+    fn arithmetic(
+        &mut self,
+        left: Code,
+        symbol: &str,
+        right: Code,
+        precedence: u8,
+    ) -> Result<Code, CodegenError> {
+        let left = self.number_operand(left)?;
+        let right = self.number_operand(right)?;
+        let text = format!(
+            "{} {symbol} {}",
+            left.at(precedence),
+            right.at(precedence + 1)
+        );
+        let kind = if left.kind == Kind::Range || right.kind == Kind::Range {
+            Kind::Range
+        } else {
+            Kind::Number
+        };
+        Ok(Code::new(text, precedence, kind))
+    }
+
+    /// `/` or `^` through the `divide` or `power` helper, which coerces its
+    /// operands itself but only handles single values
+    fn scalar_arithmetic(
+        &mut self,
+        helper: &'static str,
+        left: Code,
+        right: Code,
+    ) -> Result<Code, CodegenError> {
+        if left.kind == Kind::Range || right.kind == Kind::Range {
+            let message = "array division or powers by anything but a literal";
+            return Err(CodegenError::Unsupported(message.into()));
+        }
+        Ok(self.call(helper, &[left.text, right.text], Kind::Number))
+    }
+
+    /// An operand of native arithmetic. Anything not known to be a number
+    /// goes through `to_number`, so a blank cell counts as 0 and numeric
+    /// text as its number. Ranges are left to numpy or pandas, which work
+    /// element-wise and keep the result a range; nested lists have no
+    /// arithmetic.
+    fn number_operand(&mut self, code: Code) -> Result<Code, CodegenError> {
+        match code.kind {
+            Kind::Number => Ok(code),
+            Kind::Range if self.options.backend == Backend::Python => {
+                let message = "array arithmetic with the Python backend";
+                Err(CodegenError::Unsupported(message.into()))
+            }
+            Kind::Range => Ok(code),
+            _ => Ok(self.call("to_number", &[code.text], Kind::Number)),
+        }
+    }
+
+    fn emit_comparison(&mut self, op: BinaryOp, left: Code, right: Code) -> Code {
+        let symbol = match op {
+            BinaryOp::Equal => "==",
+            BinaryOp::NotEqual => "!=",
+            other => other.symbol(),
+        };
+
+        // Python chains comparisons, so operands are always parenthesized
+        // when they are comparisons themselves
+        let native = matches!(
+            (left.kind, right.kind),
+            (Kind::Number, Kind::Number) | (Kind::Bool, Kind::Bool)
+        );
+        let text = if native {
+            format!(
+                "{} {symbol} {}",
+                left.at(COMPARISON + 1),
+                right.at(COMPARISON + 1)
+            )
+        } else {
+            let ordering = self.call("compare", &[left.text, right.text], Kind::Number);
+            format!("{} {symbol} 0", ordering.text)
+        };
+        Code::new(text, COMPARISON, Kind::Bool)
+    }
+
+    fn emit_call(&mut self, name: &str, args: &[Expr]) -> Result<Code, CodegenError> {
+        let Some(function) = functions::lookup(name) else {
+            return Err(CodegenError::Unsupported(format!("function {name}")));
+        };
+        let kind = match function.returns {
+            ReturnType::Number => Kind::Number,
+            ReturnType::Text => Kind::Text,
+            ReturnType::Bool => Kind::Bool,
+            ReturnType::Array => Kind::Range,
+            ReturnType::Any | ReturnType::Reference => Kind::Any,
+        };
+
+        if name == "INDEX"
+            && let Some(reference) = index_reference(args)
+        {
+            return self.emit_reference(&reference);
+        }
+        let helper = match function.python {
+            Translation::Helper(helper) => helper,
+            Translation::Custom(_) => return self.emit_custom_call(name, args, kind),
+        };
+        if name == "SUM" {
+            return self.emit_sum(args);
+        }
+        let args = args
+            .iter()
+            .enumerate()
+            .map(|(index, arg)| match (function.arg(index), arg) {
+                (Some(Arg::Criteria), Expr::Literal(literal)) => Ok(self.emit_criterion(literal)),
+                (Some(Arg::Text), Expr::Missing) => Ok(Code::new(string(""), ATOM, Kind::Text)),
+                _ => self.emit_code(arg),
+            })
+            .collect::<Result<Vec<_>, CodegenError>>()?;
+        let args: Vec<String> = args.into_iter().map(|arg| arg.text).collect();
+        Ok(self.call(helper, &args, kind))
+    }
+
+    /// Functions with [`Translation::Custom`], written without a helper call
+    fn emit_custom_call(
+        &mut self,
+        name: &str,
+        args: &[Expr],
+        kind: Kind,
+    ) -> Result<Code, CodegenError> {
+        match (name, args) {
+            ("NA", _) => Ok(self.call("ExcelError", &[string("#N/A")], kind)),
+            ("POWER", [number, power]) => self.emit_binary(BinaryOp::Power, number, power),
+            ("IF", [condition, then]) => {
+                let then = self.emit_code(then)?;
+                self.emit_if(condition, then, None)
+            }
+            ("IF", [condition, then, otherwise]) => {
+                let then = self.emit_code(then)?;
+                let otherwise = self.emit_code(otherwise)?;
+                self.emit_if(condition, then, Some(otherwise))
+            }
+            ("IFS", _) => {
+                // Built from the last pair outwards: IFS(a,1,b,2) is
+                // IF(a,1,IF(b,2,NA()))
+                let mut otherwise = self.call("ExcelError", &[string("#N/A")], Kind::Any);
+                for pair in args.chunks(2).rev() {
+                    let then = self.emit_code(&pair[1])?;
+                    otherwise = self.emit_if(&pair[0], then, Some(otherwise))?;
+                }
+                Ok(otherwise)
+            }
+            ("TEXT", [value, format]) => {
+                let code = match format {
+                    Expr::Literal(Literal::String(code)) => code.as_str(),
+                    Expr::Missing => "",
+                    _ => {
+                        let message = "TEXT with a format that is not a string literal";
+                        return Err(CodegenError::Unsupported(message.into()));
+                    }
+                };
+                let format = match NumberFormat::parse(code) {
+                    Ok(format) => format,
+                    Err(FormatError::Fraction) => {
+                        let message = "TEXT with a fraction format";
+                        return Err(CodegenError::Unsupported(message.into()));
+                    }
+                    // A format Excel cannot read makes TEXT #VALUE!
+                    Err(_) => return Ok(self.call("ExcelError", &[string("#VALUE!")], kind)),
+                };
+                let sections: Vec<String> = format.numbers.iter().map(python_section).collect();
+                let mut args = vec![
+                    self.emit_code(value)?.text,
+                    format!("[{}]", sections.join(", ")),
+                ];
+                if let Some(text) = &format.text {
+                    args.push(python_parts(&text.parts));
+                }
+                Ok(self.call("text_format", &args, kind))
+            }
+            _ => Err(CodegenError::Unsupported(format!("function {name}"))),
+        }
+    }
+
+    /// A constant criterion, parsed now so the generated code builds the
+    /// test directly: `">=10"` becomes `criterion(">=", 10)`
+    fn emit_criterion(&mut self, literal: &Literal) -> Code {
+        let (op, operand) = match literal {
+            Literal::String(text) => {
+                let criterion = Criterion::parse(text);
+                let operand = match criterion.operand {
+                    Operand::Blank => "None".to_string(),
+                    Operand::Number(n) => number(n),
+                    Operand::Bool(b) => self.emit_literal(&Literal::Bool(b)).text,
+                    Operand::Error(code) => self.emit_literal(&Literal::Error(code)).text,
+                    Operand::Text(text) => string(&text),
+                };
+                (criterion.op.symbol(), operand)
+            }
+            literal => ("=", self.emit_literal(literal).text),
+        };
+        self.call("criterion", &[string(op), operand], Kind::Any)
+    }
+
+    /// IF as a conditional expression, so only the chosen branch runs. The
+    /// coerced condition is bound to `cond` to return it when it is an
+    /// error; an omitted `otherwise` is FALSE, which is `cond` itself then.
+    fn emit_if(
+        &mut self,
+        condition: &Expr,
+        then: Code,
+        otherwise: Option<Code>,
+    ) -> Result<Code, CodegenError> {
+        let condition = self.emit_code(condition)?;
+        let condition = self.call("to_bool", &[condition.text], Kind::Bool);
+        let kind = match &otherwise {
+            Some(otherwise) if otherwise.kind == then.kind => then.kind,
+            None if then.kind == Kind::Bool => Kind::Bool,
+            _ => Kind::Any,
+        };
+        let mut text = format!(
+            "{} if (cond := {}) is True else cond",
+            then.at(CONDITIONAL + 1),
+            condition.text
+        );
+        if let Some(otherwise) = otherwise {
+            self.helpers.insert("iserror");
+            text.push_str(&format!(
+                " if iserror(cond) else {}",
+                otherwise.at(CONDITIONAL)
+            ));
+        }
+        Ok(Code::new(text, CONDITIONAL, kind))
+    }
+
+    /// SUM. Cell references go in as one-cell ranges, so text and logicals
+    /// in them are skipped like in any range rather than coerced. With numpy
+    /// or pandas, the ranges written here and plain numbers are added
+    /// natively; names, function results and the rest go through `sum_`.
+    fn emit_sum(&mut self, args: &[Expr]) -> Result<Code, CodegenError> {
+        let native = self.options.backend != Backend::Python;
+        let mut terms = Vec::new();
+        let mut rest = Vec::new();
+        for arg in args {
+            let code = self.emit_code(arg)?;
+            match arg {
+                Expr::Reference(Reference {
+                    kind: ReferenceKind::Cell(_),
+                    ..
+                }) => rest.push(format!("[[{}]]", code.text)),
+                Expr::Call { .. } => rest.push(code.text),
+                _ if native && code.kind == Kind::Range => terms.push(self.emit_range_sum(code)),
+                _ if native && code.kind == Kind::Number => terms.push(code),
+                _ => rest.push(code.text),
+            }
+        }
+        if !rest.is_empty() {
+            terms.push(self.call("sum_", &rest, Kind::Number));
+        }
+        if terms.len() == 1 {
+            let term = terms.pop().unwrap();
+            return Ok(Code::new(term.text, term.precedence, Kind::Number));
+        }
+        let text = terms
+            .iter()
+            .map(|term| term.at(ADDITIVE + 1))
+            .collect::<Vec<_>>()
+            .join(" + ");
+        Ok(Code::new(text, ADDITIVE, Kind::Number))
+    }
+
+    /// The SUM of a numpy array or DataFrame with the backend's own
+    /// aggregation, after `range_numbers` drops the cells SUM skips
+    fn emit_range_sum(&mut self, range: Code) -> Code {
+        let numbers = self.call("range_numbers", &[range.text], Kind::Range);
+        let text = match self.options.backend {
+            Backend::Pandas => format!("{}.sum().sum()", numbers.text),
+            _ => {
+                self.imports.insert("import numpy as np");
+                format!("np.sum({})", numbers.text)
+            }
+        };
+        Code::new(text, ATOM, Kind::Number)
+    }
+
+    /// `&` chains are flattened into a single join: plain `+` when every
+    /// part is known to be text, otherwise `concat()` with coercion
+    fn emit_concat(&mut self, left: &Expr, right: &Expr) -> Result<Code, CodegenError> {
+        let mut parts = Vec::new();
+        self.concat_parts(left, &mut parts)?;
+        self.concat_parts(right, &mut parts)?;
+
+        if parts.iter().all(|part| part.kind == Kind::Text) {
+            let text = parts
+                .iter()
+                .map(|part| part.at(ADDITIVE + 1))
+                .collect::<Vec<_>>()
+                .join(" + ");
+            return Ok(Code::new(text, ADDITIVE, Kind::Text));
+        }
+        let args: Vec<String> = parts.into_iter().map(|part| part.text).collect();
+        Ok(self.call("concat", &args, Kind::Text))
+    }
+
+    fn concat_parts(&mut self, expr: &Expr, parts: &mut Vec<Code>) -> Result<(), CodegenError> {
+        match expr {
+            Expr::Binary {
+                op: BinaryOp::Concat,
+                left,
+                right,
+            } => {
+                self.concat_parts(left, parts)?;
+                self.concat_parts(right, parts)
+            }
+            expr => {
+                parts.push(self.emit_code(expr)?);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// An end of a `:` range as a reference: a reference itself, or an INDEX
+/// call that [`index_reference`] resolves
+fn range_end(expr: &Expr) -> Option<Cow<'_, Reference>> {
+    match expr {
+        Expr::Reference(reference) => Some(Cow::Borrowed(reference)),
+        Expr::Call { name, args, .. } if name == "INDEX" => index_reference(args).map(Cow::Owned),
+        _ => None,
+    }
+}
+
+/// The cell `INDEX(array, row, column)` picks when the array is a reference
+/// and the positions are number literals, so `INDEX(A:A,3)` is `A3`. `None`
+/// when the cell is only known at run time, or the result is not one cell.
+fn index_reference(args: &[Expr]) -> Option<Reference> {
+    let (array, row, column) = match args {
+        [array, row] => (array, literal_number(row)?, None),
+        [array, row, column] => (array, literal_number(row)?, Some(literal_number(column)?)),
+        _ => return None,
+    };
+    let (reference, rows, columns) = match array {
+        Expr::Reference(reference) => match &reference.kind {
+            ReferenceKind::Cell(cell) => {
+                (reference, (cell.row, cell.row), (cell.column, cell.column))
+            }
+            ReferenceKind::Rows(first, last) => {
+                (reference, (first.index, last.index), (1, MAX_COLUMN))
+            }
+            ReferenceKind::Columns(first, last) => {
+                (reference, (1, MAX_ROW), (first.index, last.index))
+            }
+            _ => return None,
+        },
+        Expr::Range(left, right) => match (left.as_ref(), right.as_ref()) {
+            (Expr::Reference(start), Expr::Reference(end))
+                if start.workbook == end.workbook && start.sheet == end.sheet =>
+            {
+                let (ReferenceKind::Cell(first), ReferenceKind::Cell(last)) =
+                    (&start.kind, &end.kind)
+                else {
+                    return None;
+                };
+                (
+                    start,
+                    (first.row.min(last.row), first.row.max(last.row)),
+                    (first.column.min(last.column), first.column.max(last.column)),
+                )
+            }
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    // In a single row or column, one position is enough
+    let (row, column) = match column {
+        Some(column) => (row, column),
+        None if rows.0 == rows.1 => (1.0, row),
+        None if columns.0 == columns.1 => (row, 1.0),
+        None => return None,
+    };
+    // Positions truncate like Excel's; 0 (a whole row or column) and
+    // positions outside the array are left to the runtime
+    let offset = |position: f64, (first, last): (u32, u32)| {
+        let position = position.trunc();
+        (position >= 1.0 && position <= f64::from(last - first + 1))
+            .then(|| first + position as u32 - 1)
+    };
+    let cell = CellRef::new(offset(column, columns)?, offset(row, rows)?);
+    Some(Reference {
+        workbook: reference.workbook,
+        sheet: reference.sheet.clone(),
+        kind: ReferenceKind::Cell(cell),
+    })
+}
+
+/// A number format section as the `(condition, scale, parts)` tuple the
+/// runtime's `text_format` takes
+fn python_section(section: &Section) -> String {
+    let condition = match &section.condition {
+        Some(condition) => format!(
+            "({}, {})",
+            string(condition.op.symbol()),
+            number(condition.value)
+        ),
+        None => "None".to_string(),
+    };
+    format!(
+        "({condition}, {}, {})",
+        section.scale,
+        python_parts(&section.parts)
+    )
+}
+
+/// Format parts as a list of `(tag, payload)` tuples
+fn python_parts(parts: &[Part]) -> String {
+    let parts: Vec<String> = parts
+        .iter()
+        .map(|part| {
+            let (tag, payload) = match part {
+                Part::Text(text) => ("text", string(text)),
+                Part::Integer(digits) => ("integer", string(digits)),
+                Part::Fraction(digits) => ("fraction", string(digits)),
+                Part::Exponent { plus, digits } => {
+                    let sign = if *plus { '+' } else { '-' };
+                    ("exponent", string(&format!("{sign}{digits}")))
+                }
+                Part::Value => ("value", "None".to_string()),
+                Part::General => ("general", "None".to_string()),
+                Part::Year(n) => ("year", n.to_string()),
+                Part::Month(n) => ("month", n.to_string()),
+                Part::Day(n) => ("day", n.to_string()),
+                Part::Hour(n) => ("hour", n.to_string()),
+                Part::Minute(n) => ("minute", n.to_string()),
+                Part::Second(n) => ("second", n.to_string()),
+                Part::Subsecond(n) => ("subsecond", n.to_string()),
+                Part::ElapsedHours(n) => ("elapsed_hours", n.to_string()),
+                Part::ElapsedMinutes(n) => ("elapsed_minutes", n.to_string()),
+                Part::ElapsedSeconds(n) => ("elapsed_seconds", n.to_string()),
+                Part::AmPm(text) => ("ampm", string(text)),
+            };
+            format!("({}, {payload})", string(tag))
+        })
+        .collect();
+    format!("[{}]", parts.join(", "))
+}
+
+/// The value of a number literal, looking through unary signs
+fn literal_number(expr: &Expr) -> Option<f64> {
+    match expr {
+        Expr::Literal(Literal::Number(n)) => Some(*n),
+        Expr::Unary {
+            op: UnaryOp::Plus,
+            operand,
+        } => literal_number(operand),
+        Expr::Unary {
+            op: UnaryOp::Negate,
+            operand,
+        } => literal_number(operand).map(|n| -n),
+        _ => None,
+    }
+}
+
+/// Python literal for a number: integral values print without a fraction
+pub fn number(n: f64) -> String {
+    if n.fract() == 0.0 && n.abs() < 1e15 {
+        format!("{}", n as i64)
+    } else {
+        format!("{n:?}")
+    }
+}
+
+/// Double-quoted Python string literal
+pub fn string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
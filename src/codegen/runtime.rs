@@ -0,0 +1,1722 @@
+//! Python support functions that generated code may call.
+//!
+//! Each helper is a self-contained snippet of Python source. Together they
+//! make up the `expy_runtime` module returned by [`module`], which generated
+//! code imports from. Code that inlines its helpers instead gets only the
+//! ones it uses, plus their dependencies, in the order of [`HELPERS`].
+
+/// Name of the Python module generated code imports helpers from
+pub const MODULE_NAME: &str = "expy_runtime";
+
+/// Version stamped into the runtime module, matching the crate's
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// A Python function the generated code can call
+#[derive(Debug)]
+pub struct Helper {
+    pub name: &'static str,
+    pub deps: &'static [&'static str],
+    /// Standard library imports the source relies on, e.g. `import math`
+    pub imports: &'static [&'static str],
+    pub source: &'static str,
+}
+
+pub const HELPERS: &[Helper] = &[
+    Helper {
+        name: "ExcelError",
+        deps: &[],
+        imports: &[],
+        source: r##"class ExcelError:
+    """An Excel error value such as #DIV/0! or #N/A.
+
+    Errors are ordinary values rather than exceptions: arithmetic and
+    comparisons involving an error evaluate to that error, and when both
+    operands are errors the left one wins, as in Excel.
+    """
+
+    TYPES = (
+        "#NULL!", "#DIV/0!", "#VALUE!", "#REF!", "#NAME?", "#NUM!", "#N/A",
+        "#GETTING_DATA", "#SPILL!", "#CONNECT!", "#BLOCKED!", "#UNKNOWN!",
+        "#FIELD!", "#CALC!",
+    )
+
+    __slots__ = ("code",)
+
+    def __init__(self, code):
+        self.code = code
+
+    def __repr__(self):
+        return f"ExcelError({self.code!r})"
+
+    def __str__(self):
+        return self.code
+
+    def __hash__(self):
+        return hash(self.code)
+
+    def _propagate(self, *_):
+        return self
+
+    __add__ = __radd__ = __sub__ = __rsub__ = _propagate
+    __mul__ = __rmul__ = __truediv__ = __rtruediv__ = _propagate
+    __pow__ = __rpow__ = __neg__ = __pos__ = _propagate
+    __eq__ = __ne__ = __lt__ = __le__ = __gt__ = __ge__ = _propagate"##,
+    },
+    Helper {
+        name: "first_error",
+        deps: &["ExcelError"],
+        imports: &[],
+        source: r##"def first_error(*values):
+    """Returns the first ExcelError among the values, or None."""
+    for value in values:
+        if isinstance(value, ExcelError):
+            return value
+    return None"##,
+    },
+    Helper {
+        name: "is_range",
+        deps: &[],
+        imports: &[],
+        source: r##"def is_range(value):
+    """Whether value is a range: nested lists, a numpy array or a pandas
+    DataFrame."""
+    return isinstance(value, (list, tuple)) or getattr(value, "ndim", 0) > 0"##,
+    },
+    Helper {
+        name: "flatten",
+        deps: &[],
+        imports: &[],
+        source: r##"def flatten(values):
+    """Yields the cells of a range row by row. NaN cells, which is how
+    pandas reads blanks, come out as None."""
+    if hasattr(values, "to_numpy"):
+        values = values.to_numpy()
+    if hasattr(values, "tolist"):
+        values = values.tolist()
+    for value in values:
+        if isinstance(value, (list, tuple)):
+            yield from flatten(value)
+        elif isinstance(value, float) and value != value:
+            yield None
+        else:
+            yield value"##,
+    },
+    Helper {
+        name: "grid",
+        deps: &[],
+        imports: &[],
+        source: r##"def grid(value):
+    """A range as a list of rows, or a single value as a 1x1 range. NaN
+    cells, which is how pandas reads blanks, become None."""
+    if hasattr(value, "to_numpy"):
+        value = value.to_numpy()
+    if hasattr(value, "tolist"):
+        value = value.tolist()
+    if not isinstance(value, (list, tuple)):
+        return [[value]]
+    rows = [row if isinstance(row, (list, tuple)) else [row] for row in value]
+    return [
+        [None if isinstance(cell, float) and cell != cell else cell for cell in row]
+        for row in rows
+    ]"##,
+    },
+    Helper {
+        name: "parse_number",
+        deps: &[],
+        imports: &["import re"],
+        source: r##"def parse_number(text):
+    """Reads text that criteria treat as a number, such as " 10", "-2.5"
+    or "1e3", or returns None."""
+    if re.fullmatch(r" *[+-]?([0-9]+\.?[0-9]*|\.[0-9]+)([eE][+-]?[0-9]+)? *", text):
+        return float(text)
+    return None"##,
+    },
+    Helper {
+        name: "to_number",
+        deps: &["ExcelError", "parse_number"],
+        imports: &[],
+        source: r##"def to_number(value):
+    """Coerces a single value to a number like Excel arithmetic: blanks
+    are 0, booleans 1 or 0, numeric text is parsed, other text is #VALUE!.
+    NaN, which is how pandas reads blanks, is 0 too."""
+    if value is None or (isinstance(value, float) and value != value):
+        return 0
+    if isinstance(value, bool):
+        return int(value)
+    if isinstance(value, str):
+        number = parse_number(value)
+        return ExcelError("#VALUE!") if number is None else number
+    return value"##,
+    },
+    Helper {
+        name: "to_text",
+        deps: &[],
+        imports: &[],
+        source: r##"def to_text(value):
+    """Converts a value to text the way Excel's & operator does."""
+    if value is None:
+        return ""
+    if isinstance(value, bool):
+        return "TRUE" if value else "FALSE"
+    if isinstance(value, (int, float)):
+        if value == int(value) and abs(value) < 1e15:
+            return str(int(value))
+        return format(value, ".15g").upper()
+    return str(value)"##,
+    },
+    Helper {
+        name: "to_bool",
+        deps: &["ExcelError"],
+        imports: &[],
+        source: r##"def to_bool(value):
+    """Coerces a single value to TRUE or FALSE like IF's condition: numbers
+    are TRUE unless 0, blanks are FALSE and text counts only when it reads
+    TRUE or FALSE, otherwise it is #VALUE!. Errors are returned as is."""
+    if isinstance(value, ExcelError):
+        return value
+    if value is None or (isinstance(value, float) and value != value):
+        return False
+    if isinstance(value, str):
+        upper = value.strip().upper()
+        if upper in ("TRUE", "FALSE"):
+            return upper == "TRUE"
+        return ExcelError("#VALUE!")
+    return bool(value)"##,
+    },
+    Helper {
+        name: "to_numbers",
+        deps: &["first_error", "to_number"],
+        imports: &[],
+        source: r##"def to_numbers(*values):
+    """Coerces function arguments with to_number. Returns them as a list,
+    or the first one that is an error."""
+    numbers = [to_number(value) for value in values]
+    error = first_error(*numbers)
+    if error is not None:
+        return error
+    return numbers"##,
+    },
+    Helper {
+        name: "concat",
+        deps: &["first_error", "to_text"],
+        imports: &[],
+        source: r##"def concat(*values):
+    """Excel's & operator: joins the text form of every value."""
+    error = first_error(*values)
+    if error is not None:
+        return error
+    return "".join(to_text(value) for value in values)"##,
+    },
+    Helper {
+        name: "compare",
+        deps: &["first_error"],
+        imports: &[],
+        source: r##"def compare(a, b):
+    """Orders two values like Excel's comparison operators.
+
+    Numbers sort before text, text before booleans, text compares
+    case-insensitively and a blank takes the type of the other side.
+    Returns a negative number, zero or a positive number, or the first
+    error operand.
+    """
+    def blank_like(other):
+        if isinstance(other, bool):
+            return False
+        if isinstance(other, str):
+            return ""
+        return 0
+
+    def rank(value):
+        if isinstance(value, bool):
+            return 2
+        if isinstance(value, str):
+            return 1
+        return 0
+
+    error = first_error(a, b)
+    if error is not None:
+        return error
+    if a is None:
+        a = blank_like(b)
+    if b is None:
+        b = blank_like(a)
+    if rank(a) != rank(b):
+        return rank(a) - rank(b)
+    if isinstance(a, str):
+        a, b = a.lower(), b.lower()
+    return (a > b) - (a < b)"##,
+    },
+    Helper {
+        name: "divide",
+        deps: &["first_error", "to_number"],
+        imports: &[],
+        source: r##"def divide(numerator, denominator):
+    """Excel's / operator: coerces its operands with to_number and gives
+    #DIV/0! instead of ZeroDivisionError."""
+    numerator, denominator = to_number(numerator), to_number(denominator)
+    error = first_error(numerator, denominator)
+    if error is not None:
+        return error
+    if not denominator:
+        return ExcelError("#DIV/0!")
+    return numerator / denominator"##,
+    },
+    Helper {
+        name: "power",
+        deps: &["first_error", "to_number"],
+        imports: &[],
+        source: r##"def power(base, exponent):
+    """Excel's ^ operator: coerces its operands with to_number and gives
+    #NUM! or #DIV/0! where Python would raise or return a complex number."""
+    base, exponent = to_number(base), to_number(exponent)
+    error = first_error(base, exponent)
+    if error is not None:
+        return error
+    if base == 0 and exponent == 0:
+        return ExcelError("#NUM!")
+    if base == 0 and exponent < 0:
+        return ExcelError("#DIV/0!")
+    try:
+        result = base ** exponent
+    except OverflowError:
+        return ExcelError("#NUM!")
+    if isinstance(result, complex):
+        return ExcelError("#NUM!")
+    return result"##,
+    },
+    Helper {
+        name: "sum_",
+        deps: &["is_range", "flatten", "to_number"],
+        imports: &[],
+        source: r##"def sum_(*values):
+    """SUM: adds its arguments. Inside ranges only numbers count; text,
+    booleans and blanks are skipped."""
+    total = 0
+    for value in values:
+        if is_range(value):
+            for cell in flatten(value):
+                if isinstance(cell, ExcelError):
+                    return cell
+                if isinstance(cell, (int, float)) and not isinstance(cell, bool):
+                    total += cell
+        else:
+            number = to_number(value)
+            if isinstance(number, ExcelError):
+                return number
+            total += number
+    return total"##,
+    },
+    Helper {
+        name: "range_numbers",
+        deps: &["ExcelError"],
+        imports: &[],
+        source: r##"def range_numbers(values):
+    """The cells of a numpy array or DataFrame that SUM adds in a range, for
+    np.sum or DataFrame.sum: numbers and errors, while text, booleans and
+    blanks are dropped from arrays and NaN in DataFrames. Numeric data is
+    returned as is, or without its NaN blanks, so the sum stays vectorized."""
+
+    def counts(cell):
+        if isinstance(cell, ExcelError):
+            return True
+        kind = getattr(getattr(cell, "dtype", None), "kind", None)
+        if kind is None:
+            number = isinstance(cell, (int, float)) and not isinstance(cell, bool)
+            return number and cell == cell
+        return kind in ("i", "u", "f") and cell == cell
+
+    if hasattr(values, "columns"):
+        if all(dtype.kind in "iuf" for dtype in values.dtypes):
+            return values
+        return values.apply(
+            lambda column: column.map(lambda cell: cell if counts(cell) else float("nan"))
+        )
+    if values.dtype.kind in "iu":
+        return values
+    if values.dtype.kind == "f":
+        return values[values == values]
+    return [cell for cell in values.flat if counts(cell)]"##,
+    },
+    Helper {
+        name: "sumproduct",
+        deps: &["ExcelError", "grid", "flatten"],
+        imports: &[],
+        source: r##"def sumproduct(*arrays):
+    """SUMPRODUCT: multiplies matching cells of equally sized ranges and
+    adds the products. Cells that are not numbers count as 0; ranges of
+    different sizes are #VALUE!."""
+    grids = [grid(array) for array in arrays]
+    shape = (len(grids[0]), len(grids[0][0]))
+    if any((len(rows), len(rows[0])) != shape for rows in grids):
+        return ExcelError("#VALUE!")
+    total = 0
+    for cells in zip(*(list(flatten(rows)) for rows in grids)):
+        product = 1
+        for cell in cells:
+            if isinstance(cell, ExcelError):
+                return cell
+            if not isinstance(cell, (int, float)) or isinstance(cell, bool):
+                cell = 0
+            product *= cell
+        total += product
+    return total"##,
+    },
+    Helper {
+        name: "round_digits",
+        deps: &["to_numbers"],
+        imports: &["import decimal"],
+        source: r##"def round_digits(number, num_digits, rounding):
+    """Rounds to num_digits decimal places, or to tens, hundreds, ... when
+    negative, with a decimal module rounding mode. Works on the number as
+    written in decimal, so ROUND(2.675, 2) is 2.68 as in Excel."""
+    args = to_numbers(number, num_digits)
+    if isinstance(args, ExcelError):
+        return args
+    number, num_digits = args
+    exponent = decimal.Decimal(1).scaleb(-int(num_digits))
+    try:
+        rounded = decimal.Decimal(str(number)).quantize(exponent, rounding=rounding)
+    except decimal.InvalidOperation:
+        # More digits than the number has
+        return number
+    return float(rounded)"##,
+    },
+    Helper {
+        name: "round_",
+        deps: &["round_digits"],
+        imports: &["import decimal"],
+        source: r##"def round_(number, num_digits):
+    """ROUND: rounds half away from zero, unlike Python's round()."""
+    return round_digits(number, num_digits, decimal.ROUND_HALF_UP)"##,
+    },
+    Helper {
+        name: "roundup",
+        deps: &["round_digits"],
+        imports: &["import decimal"],
+        source: r##"def roundup(number, num_digits):
+    """ROUNDUP: rounds away from zero."""
+    return round_digits(number, num_digits, decimal.ROUND_UP)"##,
+    },
+    Helper {
+        name: "rounddown",
+        deps: &["round_digits"],
+        imports: &["import decimal"],
+        source: r##"def rounddown(number, num_digits):
+    """ROUNDDOWN: rounds toward zero."""
+    return round_digits(number, num_digits, decimal.ROUND_DOWN)"##,
+    },
+    Helper {
+        name: "round_multiple",
+        deps: &[],
+        imports: &["import decimal"],
+        source: r##"def round_multiple(number, multiple, rounding):
+    """Rounds number to a multiple of multiple with a decimal module
+    rounding mode, working in decimal like round_digits."""
+    if multiple == 0:
+        return 0
+    multiple = decimal.Decimal(str(multiple))
+    quotient = decimal.Decimal(str(number)) / multiple
+    return float(quotient.quantize(decimal.Decimal(1), rounding=rounding) * multiple)"##,
+    },
+    Helper {
+        name: "mround",
+        deps: &["to_numbers", "round_multiple"],
+        imports: &["import decimal"],
+        source: r##"def mround(number, multiple):
+    """MROUND: rounds half away from zero to a multiple; #NUM! when the
+    signs differ."""
+    args = to_numbers(number, multiple)
+    if isinstance(args, ExcelError):
+        return args
+    number, multiple = args
+    if number * multiple < 0:
+        return ExcelError("#NUM!")
+    return round_multiple(number, multiple, decimal.ROUND_HALF_UP)"##,
+    },
+    Helper {
+        name: "ceiling_math",
+        deps: &["to_numbers", "round_multiple"],
+        imports: &["import decimal"],
+        source: r##"def ceiling_math(number, significance=1, mode=0):
+    """CEILING.MATH: rounds up to a multiple of significance. Negative
+    numbers round toward zero, or away from it when mode is nonzero."""
+    args = to_numbers(number, significance, mode)
+    if isinstance(args, ExcelError):
+        return args
+    number, significance, mode = args
+    if number < 0 and mode:
+        rounding = decimal.ROUND_UP
+    else:
+        rounding = decimal.ROUND_CEILING
+    return round_multiple(number, abs(significance), rounding)"##,
+    },
+    Helper {
+        name: "floor_math",
+        deps: &["to_numbers", "round_multiple"],
+        imports: &["import decimal"],
+        source: r##"def floor_math(number, significance=1, mode=0):
+    """FLOOR.MATH: rounds down to a multiple of significance. Negative
+    numbers round away from zero, or toward it when mode is nonzero."""
+    args = to_numbers(number, significance, mode)
+    if isinstance(args, ExcelError):
+        return args
+    number, significance, mode = args
+    if number < 0 and mode:
+        rounding = decimal.ROUND_DOWN
+    else:
+        rounding = decimal.ROUND_FLOOR
+    return round_multiple(number, abs(significance), rounding)"##,
+    },
+    Helper {
+        name: "mod",
+        deps: &["to_numbers"],
+        imports: &[],
+        source: r##"def mod(number, divisor):
+    """MOD: the remainder with the sign of the divisor, which is how
+    Python's % already works; #DIV/0! for a zero divisor."""
+    args = to_numbers(number, divisor)
+    if isinstance(args, ExcelError):
+        return args
+    number, divisor = args
+    if divisor == 0:
+        return ExcelError("#DIV/0!")
+    return number % divisor"##,
+    },
+    Helper {
+        name: "int_",
+        deps: &["to_number"],
+        imports: &["import math"],
+        source: r##"def int_(number):
+    """INT: rounds down toward negative infinity, so INT(-2.5) is -3."""
+    number = to_number(number)
+    if isinstance(number, ExcelError):
+        return number
+    return math.floor(number)"##,
+    },
+    Helper {
+        name: "abs_",
+        deps: &["to_number"],
+        imports: &[],
+        source: r##"def abs_(number):
+    """ABS: the absolute value."""
+    number = to_number(number)
+    if isinstance(number, ExcelError):
+        return number
+    return abs(number)"##,
+    },
+    Helper {
+        name: "wildcard_pattern",
+        deps: &[],
+        imports: &["import re"],
+        source: r##"def wildcard_pattern(text):
+    """Compiles text with Excel wildcards to a case-insensitive regex: *
+    matches any run of characters, ? any one character and ~ escapes the
+    next one."""
+    parts = []
+    escaped = False
+    for char in text:
+        if escaped or char not in "~*?":
+            parts.append(re.escape(char))
+            escaped = False
+        elif char == "~":
+            escaped = True
+        else:
+            parts.append(".*" if char == "*" else ".")
+    if escaped:
+        parts.append("~")
+    return re.compile("".join(parts), re.IGNORECASE | re.DOTALL)"##,
+    },
+    Helper {
+        name: "criterion",
+        deps: &["ExcelError", "parse_number", "wildcard_pattern"],
+        imports: &["import operator"],
+        source: r##"def criterion(op, operand):
+    """A function testing one cell against a parsed criterion, such as
+    criterion(">=", 10) for ">=10".
+
+    op is one of = <> < > <= >=. operand is a number, bool, ExcelError or
+    text, or None to match blank cells. Cells only match operands of their
+    own type, except that = also finds numbers stored as text. Text
+    compares case-insensitively; with = and <> a * matches any run of
+    characters, ? any one character and ~ escapes the next one.
+    """
+    if op == "<>":
+        equal = criterion("=", operand)
+        return lambda cell: not equal(cell)
+    if operand is None:
+        return lambda cell: cell is None
+    if isinstance(operand, ExcelError):
+        code = operand.code if op == "=" else None
+        return lambda cell: isinstance(cell, ExcelError) and cell.code == code
+    if op == "=" and isinstance(operand, str):
+        if operand == "":
+            return lambda cell: cell is None or cell == ""
+        pattern = wildcard_pattern(operand)
+        return lambda cell: isinstance(cell, str) and pattern.fullmatch(cell) is not None
+
+    relation = {
+        "=": operator.eq, "<": operator.lt, ">": operator.gt,
+        "<=": operator.le, ">=": operator.ge,
+    }[op]
+
+    def comparable(cell):
+        if isinstance(operand, bool):
+            return cell if isinstance(cell, bool) else None
+        if isinstance(operand, str):
+            return cell.lower() if isinstance(cell, str) else None
+        if isinstance(cell, str) and op == "=":
+            return parse_number(cell)
+        if isinstance(cell, (int, float)) and not isinstance(cell, bool):
+            return cell
+        return None
+
+    target = operand.lower() if isinstance(operand, str) else operand
+    return lambda cell: (value := comparable(cell)) is not None and relation(value, target)"##,
+    },
+    Helper {
+        name: "parse_criteria",
+        deps: &["ExcelError", "parse_number", "criterion"],
+        imports: &[],
+        source: r##"def parse_criteria(value):
+    """The criterion function for a criteria argument. Text such as ">=10"
+    or "a*" is parsed, any other value must be matched exactly and a blank
+    counts as 0. An already parsed criterion is returned as is."""
+    if callable(value):
+        return value
+    if not isinstance(value, str):
+        return criterion("=", 0 if value is None else value)
+    for op in ("<=", ">=", "<>", "<", ">", "="):
+        if value.startswith(op):
+            text = value[len(op):]
+            if text == "":
+                return criterion(op, None)
+            break
+    else:
+        op, text = "=", value
+    number = parse_number(text)
+    if number is not None:
+        operand = number
+    elif text.upper() in ("TRUE", "FALSE"):
+        operand = text.upper() == "TRUE"
+    elif text.upper() in ExcelError.TYPES:
+        operand = ExcelError(text.upper())
+    else:
+        operand = text
+    return criterion(op, operand)"##,
+    },
+    Helper {
+        name: "criteria_values",
+        deps: &["ExcelError", "grid", "flatten", "parse_criteria"],
+        imports: &[],
+        source: r##"def criteria_values(values, pairs):
+    """The cells of values at the positions matching every (range,
+    criteria) pair, or #VALUE! when a range differs in size from values."""
+    rows = grid(values)
+    shape = (len(rows), len(rows[0]))
+    tests = []
+    for criteria_range, criteria in zip(pairs[::2], pairs[1::2]):
+        criteria_rows = grid(criteria_range)
+        if (len(criteria_rows), len(criteria_rows[0])) != shape:
+            return ExcelError("#VALUE!")
+        tests.append((list(flatten(criteria_rows)), parse_criteria(criteria)))
+    return [
+        cell
+        for position, cell in enumerate(flatten(rows))
+        if all(test(cells[position]) for cells, test in tests)
+    ]"##,
+    },
+    Helper {
+        name: "numeric_cells",
+        deps: &["ExcelError"],
+        imports: &[],
+        source: r##"def numeric_cells(cells):
+    """The numbers among cells, skipping text, booleans and blanks the way
+    SUM does in ranges. Returns the first error instead, including cells
+    itself when it is one."""
+    if isinstance(cells, ExcelError):
+        return cells
+    numbers = []
+    for cell in cells:
+        if isinstance(cell, ExcelError):
+            return cell
+        if isinstance(cell, (int, float)) and not isinstance(cell, bool):
+            numbers.append(cell)
+    return numbers"##,
+    },
+    Helper {
+        name: "resize",
+        deps: &["grid"],
+        imports: &[],
+        source: r##"def resize(values, shape):
+    """values cut or padded with blanks to shape (rows, columns) from its
+    top-left cell, the way SUMIF's sum_range follows the size of range."""
+    rows = grid(values)
+    height, width = shape
+    return [
+        [
+            rows[row][column] if row < len(rows) and column < len(rows[row]) else None
+            for column in range(width)
+        ]
+        for row in range(height)
+    ]"##,
+    },
+    Helper {
+        name: "countifs",
+        deps: &["ExcelError", "criteria_values"],
+        imports: &[],
+        source: r##"def countifs(*pairs):
+    """COUNTIFS: how many positions match every (range, criteria) pair."""
+    cells = criteria_values(pairs[0], pairs)
+    if isinstance(cells, ExcelError):
+        return cells
+    return len(cells)"##,
+    },
+    Helper {
+        name: "countif",
+        deps: &["countifs"],
+        imports: &[],
+        source: r##"def countif(criteria_range, criteria):
+    """COUNTIF: how many cells match the criteria."""
+    return countifs(criteria_range, criteria)"##,
+    },
+    Helper {
+        name: "sumifs",
+        deps: &["ExcelError", "criteria_values", "numeric_cells"],
+        imports: &[],
+        source: r##"def sumifs(sum_range, *pairs):
+    """SUMIFS: adds the numbers in sum_range where every (range, criteria)
+    pair matches."""
+    numbers = numeric_cells(criteria_values(sum_range, pairs))
+    if isinstance(numbers, ExcelError):
+        return numbers
+    return sum(numbers)"##,
+    },
+    Helper {
+        name: "sumif",
+        deps: &["grid", "resize", "sumifs"],
+        imports: &[],
+        source: r##"def sumif(criteria_range, criteria, sum_range=None):
+    """SUMIF: adds the numbers in sum_range, or in criteria_range itself,
+    where criteria_range matches the criteria."""
+    if sum_range is None:
+        sum_range = criteria_range
+    else:
+        rows = grid(criteria_range)
+        sum_range = resize(sum_range, (len(rows), len(rows[0])))
+    return sumifs(sum_range, criteria_range, criteria)"##,
+    },
+    Helper {
+        name: "averageifs",
+        deps: &["ExcelError", "criteria_values", "numeric_cells"],
+        imports: &[],
+        source: r##"def averageifs(average_range, *pairs):
+    """AVERAGEIFS: the mean of the numbers in average_range where every
+    (range, criteria) pair matches; #DIV/0! when there are none."""
+    numbers = numeric_cells(criteria_values(average_range, pairs))
+    if isinstance(numbers, ExcelError):
+        return numbers
+    if not numbers:
+        return ExcelError("#DIV/0!")
+    return sum(numbers) / len(numbers)"##,
+    },
+    Helper {
+        name: "averageif",
+        deps: &["grid", "resize", "averageifs"],
+        imports: &[],
+        source: r##"def averageif(criteria_range, criteria, average_range=None):
+    """AVERAGEIF: the mean of the numbers in average_range, or in
+    criteria_range itself, where criteria_range matches the criteria."""
+    if average_range is None:
+        average_range = criteria_range
+    else:
+        rows = grid(criteria_range)
+        average_range = resize(average_range, (len(rows), len(rows[0])))
+    return averageifs(average_range, criteria_range, criteria)"##,
+    },
+    Helper {
+        name: "maxifs",
+        deps: &["ExcelError", "criteria_values", "numeric_cells"],
+        imports: &[],
+        source: r##"def maxifs(max_range, *pairs):
+    """MAXIFS: the largest number in max_range where every (range,
+    criteria) pair matches, or 0 when there is none."""
+    numbers = numeric_cells(criteria_values(max_range, pairs))
+    if isinstance(numbers, ExcelError):
+        return numbers
+    return max(numbers, default=0)"##,
+    },
+    Helper {
+        name: "minifs",
+        deps: &["ExcelError", "criteria_values", "numeric_cells"],
+        imports: &[],
+        source: r##"def minifs(min_range, *pairs):
+    """MINIFS: the smallest number in min_range where every (range,
+    criteria) pair matches, or 0 when there is none."""
+    numbers = numeric_cells(criteria_values(min_range, pairs))
+    if isinstance(numbers, ExcelError):
+        return numbers
+    return min(numbers, default=0)"##,
+    },
+    Helper {
+        name: "lookup_position",
+        deps: &["ExcelError", "compare", "criterion"],
+        imports: &[],
+        source: r##"def lookup_position(lookup_value, values, match_mode=0, search_mode=1):
+    """The 0-based position of lookup_value in the list values, or None,
+    with XMATCH's modes.
+
+    match_mode 0 finds an exact match, -1 an exact match or else the next
+    smaller value, 1 the next larger value, and 2 also treats * ? ~ in a
+    text lookup_value as wildcards. search_mode 1 searches first to last,
+    -1 last to first, and 2 or -2 use a binary search over values sorted
+    ascending or descending; only values of the same type as lookup_value
+    can match.
+    """
+    def kind(value):
+        if isinstance(value, bool):
+            return bool
+        if isinstance(value, str):
+            return str
+        if isinstance(value, (int, float)):
+            return float
+        return None
+
+    def comparable(value):
+        return kind(value) is not None and kind(value) == kind(lookup_value)
+
+    if search_mode in (2, -2):
+        low, high = 0, len(values) - 1
+        equal = below = above = None
+        while low <= high:
+            middle = (low + high) // 2
+            order = compare(values[middle], lookup_value)
+            if isinstance(order, ExcelError):
+                order = 1
+            if search_mode == -2:
+                order = -order
+            if order == 0 and comparable(values[middle]):
+                equal = middle
+                low = middle + 1
+            elif order <= 0:
+                below = middle
+                low = middle + 1
+            else:
+                above = middle
+                high = middle - 1
+        smaller, larger = (below, above) if search_mode == 2 else (above, below)
+        if equal is not None or match_mode in (0, 2):
+            return equal
+        return smaller if match_mode == -1 else larger
+
+    if match_mode == 2 and isinstance(lookup_value, str):
+        matches = criterion("=", lookup_value)
+    else:
+        def matches(value):
+            return comparable(value) and compare(value, lookup_value) == 0
+
+    if search_mode == -1:
+        positions = range(len(values) - 1, -1, -1)
+    else:
+        positions = range(len(values))
+    best = None
+    for position in positions:
+        value = values[position]
+        if matches(value):
+            return position
+        if match_mode in (-1, 1) and comparable(value):
+            # The closest value on the side match_mode asks for
+            if compare(value, lookup_value) * match_mode > 0 and (
+                best is None or compare(value, values[best]) * match_mode < 0
+            ):
+                best = position
+    return best"##,
+    },
+    Helper {
+        name: "lookup_vector",
+        deps: &["grid"],
+        imports: &[],
+        source: r##"def lookup_vector(values):
+    """The cells of a one-row or one-column range as a list, or None for a
+    range with several rows and columns."""
+    rows = grid(values)
+    if len(rows) == 1:
+        return list(rows[0])
+    if all(len(row) == 1 for row in rows):
+        return [row[0] for row in rows]
+    return None"##,
+    },
+    Helper {
+        name: "match_",
+        deps: &[
+            "ExcelError",
+            "to_number",
+            "lookup_position",
+            "lookup_vector",
+        ],
+        imports: &[],
+        source: r##"def match_(lookup_value, lookup_array, match_type=1):
+    """MATCH: the 1-based position of lookup_value. match_type 1 finds the
+    largest value at most lookup_value in ascending data, -1 the smallest
+    value at least lookup_value in descending data, and 0 an exact match,
+    with wildcards for text. #N/A when nothing matches."""
+    match_type = to_number(match_type)
+    for value in (lookup_value, match_type):
+        if isinstance(value, ExcelError):
+            return value
+    values = lookup_vector(lookup_array)
+    if values is None:
+        return ExcelError("#N/A")
+    if match_type > 0:
+        position = lookup_position(lookup_value, values, -1, 2)
+    elif match_type < 0:
+        position = lookup_position(lookup_value, values, 1, -2)
+    else:
+        position = lookup_position(lookup_value, values, 2)
+    return ExcelError("#N/A") if position is None else position + 1"##,
+    },
+    Helper {
+        name: "xmatch",
+        deps: &[
+            "ExcelError",
+            "to_numbers",
+            "lookup_position",
+            "lookup_vector",
+        ],
+        imports: &[],
+        source: r##"def xmatch(lookup_value, lookup_array, match_mode=0, search_mode=1):
+    """XMATCH: the 1-based position of lookup_value, with the modes of
+    lookup_position; #N/A when nothing matches."""
+    modes = to_numbers(match_mode, search_mode)
+    for value in (lookup_value, modes):
+        if isinstance(value, ExcelError):
+            return value
+    match_mode, search_mode = modes
+    if match_mode not in (-1, 0, 1, 2) or search_mode not in (-2, -1, 1, 2):
+        return ExcelError("#VALUE!")
+    values = lookup_vector(lookup_array)
+    if values is None:
+        return ExcelError("#VALUE!")
+    position = lookup_position(lookup_value, values, match_mode, search_mode)
+    return ExcelError("#N/A") if position is None else position + 1"##,
+    },
+    Helper {
+        name: "table_lookup",
+        deps: &["ExcelError", "to_number", "to_bool", "lookup_position"],
+        imports: &[],
+        source: r##"def table_lookup(lookup_value, rows, index_num, range_lookup):
+    """VLOOKUP over a list of rows: finds lookup_value in the first column
+    and returns the cell in column index_num of that row."""
+    index_num = to_number(index_num)
+    range_lookup = to_bool(range_lookup)
+    for value in (lookup_value, index_num, range_lookup):
+        if isinstance(value, ExcelError):
+            return value
+    if index_num < 1:
+        return ExcelError("#VALUE!")
+    if index_num > len(rows[0]):
+        return ExcelError("#REF!")
+    keys = [row[0] for row in rows]
+    if range_lookup:
+        position = lookup_position(lookup_value, keys, -1, 2)
+    else:
+        position = lookup_position(lookup_value, keys, 2)
+    if position is None:
+        return ExcelError("#N/A")
+    return rows[position][int(index_num) - 1]"##,
+    },
+    Helper {
+        name: "vlookup",
+        deps: &["grid", "table_lookup"],
+        imports: &[],
+        source: r##"def vlookup(lookup_value, table_array, col_index_num, range_lookup=True):
+    """VLOOKUP: looks lookup_value up in the first column of table_array,
+    in sorted data unless range_lookup is FALSE; #N/A when it is missing."""
+    return table_lookup(lookup_value, grid(table_array), col_index_num, range_lookup)"##,
+    },
+    Helper {
+        name: "hlookup",
+        deps: &["grid", "table_lookup"],
+        imports: &[],
+        source: r##"def hlookup(lookup_value, table_array, row_index_num, range_lookup=True):
+    """HLOOKUP: VLOOKUP along the first row of table_array."""
+    columns = [list(column) for column in zip(*grid(table_array))]
+    return table_lookup(lookup_value, columns, row_index_num, range_lookup)"##,
+    },
+    Helper {
+        name: "xlookup",
+        deps: &[
+            "ExcelError",
+            "grid",
+            "to_numbers",
+            "lookup_position",
+            "lookup_vector",
+        ],
+        imports: &[],
+        source: r##"def xlookup(
+    lookup_value,
+    lookup_array,
+    return_array,
+    if_not_found=ExcelError("#N/A"),
+    match_mode=0,
+    search_mode=1,
+):
+    """XLOOKUP: the cells of return_array beside lookup_value's position in
+    lookup_array, with the modes of lookup_position. A return_array wider
+    than one cell gives a row or column of results."""
+    modes = to_numbers(match_mode, search_mode)
+    for value in (lookup_value, modes):
+        if isinstance(value, ExcelError):
+            return value
+    match_mode, search_mode = modes
+    if match_mode not in (-1, 0, 1, 2) or search_mode not in (-2, -1, 1, 2):
+        return ExcelError("#VALUE!")
+    values = lookup_vector(lookup_array)
+    rows = grid(return_array)
+    if values is None:
+        return ExcelError("#VALUE!")
+    vertical = len(grid(lookup_array)) == len(values) and len(values) > 1
+    if len(rows if vertical else rows[0]) != len(values):
+        return ExcelError("#VALUE!")
+    position = lookup_position(lookup_value, values, match_mode, search_mode)
+    if position is None:
+        return if_not_found
+    found = rows[position] if vertical else [row[position] for row in rows]
+    if len(found) == 1:
+        return found[0]
+    return [found] if vertical else [[cell] for cell in found]"##,
+    },
+    Helper {
+        name: "index",
+        deps: &["ExcelError", "grid", "to_numbers"],
+        imports: &[],
+        source: r##"def index(array, row_num, column_num=None, area_num=1):
+    """INDEX: the cell at row_num and column_num of array, both 1-based. A
+    0 selects a whole column or row; in a one-row array row_num picks the
+    column. #REF! outside the array."""
+    rows = grid(array)
+    if column_num is None:
+        if len(rows) == 1:
+            row_num, column_num = 1, row_num
+        else:
+            column_num = 1 if len(rows[0]) == 1 else 0
+    args = to_numbers(row_num, column_num, area_num)
+    if isinstance(args, ExcelError):
+        return args
+    row_num, column_num, area_num = (int(arg) for arg in args)
+    if area_num != 1 or not 0 <= row_num <= len(rows) or not 0 <= column_num <= len(rows[0]):
+        return ExcelError("#REF!")
+    if row_num and column_num:
+        return rows[row_num - 1][column_num - 1]
+    if row_num:
+        return [rows[row_num - 1]]
+    if column_num:
+        return [[row[column_num - 1]] for row in rows]
+    return rows"##,
+    },
+    Helper {
+        name: "left",
+        deps: &["ExcelError", "first_error", "to_number", "to_text"],
+        imports: &[],
+        source: r##"def left(text, num_chars=1):
+    """LEFT: the first num_chars characters of text."""
+    num_chars = to_number(num_chars)
+    error = first_error(text, num_chars)
+    if error is not None:
+        return error
+    if num_chars < 0:
+        return ExcelError("#VALUE!")
+    return to_text(text)[: int(num_chars)]"##,
+    },
+    Helper {
+        name: "right",
+        deps: &["ExcelError", "first_error", "to_number", "to_text"],
+        imports: &[],
+        source: r##"def right(text, num_chars=1):
+    """RIGHT: the last num_chars characters of text."""
+    num_chars = to_number(num_chars)
+    error = first_error(text, num_chars)
+    if error is not None:
+        return error
+    if num_chars < 0:
+        return ExcelError("#VALUE!")
+    text = to_text(text)
+    return text[len(text) - min(int(num_chars), len(text)) :]"##,
+    },
+    Helper {
+        name: "mid",
+        deps: &["ExcelError", "first_error", "to_numbers", "to_text"],
+        imports: &[],
+        source: r##"def mid(text, start_num, num_chars):
+    """MID: num_chars characters of text from the 1-based start_num."""
+    numbers = to_numbers(start_num, num_chars)
+    error = first_error(text, numbers)
+    if error is not None:
+        return error
+    start_num, num_chars = (int(number) for number in numbers)
+    if start_num < 1 or num_chars < 0:
+        return ExcelError("#VALUE!")
+    return to_text(text)[start_num - 1 : start_num - 1 + num_chars]"##,
+    },
+    Helper {
+        name: "find",
+        deps: &["ExcelError", "first_error", "to_number", "to_text"],
+        imports: &[],
+        source: r##"def find(find_text, within_text, start_num=1):
+    """FIND: the 1-based position of find_text in within_text, searching
+    case-sensitively from start_num; #VALUE! when it is missing."""
+    start_num = to_number(start_num)
+    error = first_error(find_text, within_text, start_num)
+    if error is not None:
+        return error
+    find_text, within_text = to_text(find_text), to_text(within_text)
+    if not 1 <= start_num <= len(within_text) + 1:
+        return ExcelError("#VALUE!")
+    position = within_text.find(find_text, int(start_num) - 1)
+    return ExcelError("#VALUE!") if position < 0 else position + 1"##,
+    },
+    Helper {
+        name: "search",
+        deps: &[
+            "ExcelError",
+            "first_error",
+            "to_number",
+            "to_text",
+            "wildcard_pattern",
+        ],
+        imports: &[],
+        source: r##"def search(find_text, within_text, start_num=1):
+    """SEARCH: FIND ignoring case, with * ? ~ wildcards in find_text."""
+    start_num = to_number(start_num)
+    error = first_error(find_text, within_text, start_num)
+    if error is not None:
+        return error
+    find_text, within_text = to_text(find_text), to_text(within_text)
+    if not 1 <= start_num <= len(within_text) + 1:
+        return ExcelError("#VALUE!")
+    found = wildcard_pattern(find_text).search(within_text, int(start_num) - 1)
+    return ExcelError("#VALUE!") if found is None else found.start() + 1"##,
+    },
+    Helper {
+        name: "substitute",
+        deps: &["ExcelError", "first_error", "to_number", "to_text"],
+        imports: &[],
+        source: r##"def substitute(text, old_text, new_text, instance_num=None):
+    """SUBSTITUTE: text with old_text replaced by new_text, everywhere or
+    only at the instance_num-th occurrence, counted from 1."""
+    if instance_num is not None:
+        instance_num = to_number(instance_num)
+    error = first_error(text, old_text, new_text, instance_num)
+    if error is not None:
+        return error
+    text, old_text, new_text = to_text(text), to_text(old_text), to_text(new_text)
+    if instance_num is None:
+        return text.replace(old_text, new_text) if old_text else text
+    if instance_num < 1:
+        return ExcelError("#VALUE!")
+    if not old_text:
+        return text
+    position = -len(old_text)
+    for _ in range(int(instance_num)):
+        position = text.find(old_text, position + len(old_text))
+        if position < 0:
+            return text
+    return text[:position] + new_text + text[position + len(old_text) :]"##,
+    },
+    Helper {
+        name: "trim",
+        deps: &["ExcelError", "to_text"],
+        imports: &[],
+        source: r##"def trim(text):
+    """TRIM: text without leading or trailing spaces, and with runs of
+    spaces inside it collapsed to one. Other whitespace is kept."""
+    if isinstance(text, ExcelError):
+        return text
+    return " ".join(part for part in to_text(text).split(" ") if part)"##,
+    },
+    Helper {
+        name: "textjoin",
+        deps: &[
+            "ExcelError",
+            "first_error",
+            "is_range",
+            "flatten",
+            "to_bool",
+            "to_text",
+        ],
+        imports: &[],
+        source: r##"def textjoin(delimiter, ignore_empty, *texts):
+    """TEXTJOIN: the texts and the cells of ranges among them joined by
+    delimiter, skipping empty text when ignore_empty is TRUE. A range of
+    delimiters is used in turn."""
+    def cells(value):
+        return list(flatten(value)) if is_range(value) else [value]
+
+    delimiters = cells(delimiter)
+    values = [cell for text in texts for cell in cells(text)]
+    ignore_empty = to_bool(ignore_empty)
+    error = first_error(ignore_empty, *delimiters, *values)
+    if error is not None:
+        return error
+    delimiters = [to_text(delimiter) for delimiter in delimiters]
+    parts = [to_text(value) for value in values]
+    if ignore_empty:
+        parts = [part for part in parts if part]
+    joined = parts[0] if parts else ""
+    for position, part in enumerate(parts[1:]):
+        joined += delimiters[position % len(delimiters)] + part
+    if len(joined) > 32767:
+        return ExcelError("#VALUE!")
+    return joined"##,
+    },
+    Helper {
+        name: "textsplit",
+        deps: &[
+            "ExcelError",
+            "first_error",
+            "is_range",
+            "flatten",
+            "to_bool",
+            "to_number",
+            "to_text",
+        ],
+        imports: &["import re"],
+        source: r##"def textsplit(
+    text,
+    col_delimiter,
+    row_delimiter=None,
+    ignore_empty=False,
+    match_mode=0,
+    pad_with=ExcelError("#N/A"),
+):
+    """TEXTSPLIT: text split into rows at row_delimiter and each row into
+    columns at col_delimiter, as a list of rows padded with pad_with.
+    Either delimiter can be a range of alternatives; match_mode 1 ignores
+    case and ignore_empty drops empty cells and rows."""
+    ignore_empty = to_bool(ignore_empty)
+    match_mode = to_number(match_mode)
+    error = first_error(text, col_delimiter, row_delimiter, ignore_empty, match_mode)
+    if error is not None:
+        return error
+
+    def splitter(delimiter):
+        if delimiter is None:
+            return None
+        alternatives = list(flatten(delimiter)) if is_range(delimiter) else [delimiter]
+        alternatives = {to_text(alternative) for alternative in alternatives} - {""}
+        if not alternatives:
+            return None
+        pattern = "|".join(re.escape(d) for d in sorted(alternatives, key=len, reverse=True))
+        return re.compile(pattern, re.IGNORECASE if match_mode else 0)
+
+    columns, rows = splitter(col_delimiter), splitter(row_delimiter)
+    if columns is None and rows is None:
+        return ExcelError("#VALUE!")
+    lines = rows.split(to_text(text)) if rows else [to_text(text)]
+    table = [columns.split(line) if columns else [line] for line in lines]
+    if ignore_empty:
+        table = [[cell for cell in row if cell] for row in table]
+        table = [row for row in table if row] or [[""]]
+    width = max(len(row) for row in table)
+    return [row + [pad_with] * (width - len(row)) for row in table]"##,
+    },
+    Helper {
+        name: "format_number",
+        deps: &["to_text"],
+        imports: &["import decimal"],
+        source: r##"def format_number(number, scale, parts):
+    """Formats a number that is not negative with the parts of a number
+    format section; see text_format."""
+    integers = "".join(p for tag, p in parts if tag == "integer").replace(",", "")
+    fractions = "".join(p for tag, p in parts if tag == "fraction")
+    exponent = next((p for tag, p in parts if tag == "exponent"), None)
+    grouping = any(tag == "integer" and "," in p for tag, p in parts)
+    value = decimal.Decimal(repr(number)).scaleb(scale)
+    step = decimal.Decimal(1).scaleb(-len(fractions))
+
+    def rounded(value):
+        return value.quantize(step, rounding=decimal.ROUND_HALF_UP)
+
+    magnitude = 0
+    if exponent is not None and value:
+        # ##0.0E+0 keeps exponents to multiples of 3; 00.0E+0 shows two
+        # integer digits
+        if "#" in integers:
+            width = len(integers)
+            magnitude = value.adjusted() // width * width
+        else:
+            width = max(len(integers), 1)
+            magnitude = value.adjusted() - width + 1
+        if rounded(value.scaleb(-magnitude)) >= 10**width:
+            magnitude += width if "#" in integers else 1
+        value = value.scaleb(-magnitude)
+    whole, _, fraction = format(rounded(value), "f").partition(".")
+    whole = "" if whole == "0" else whole
+
+    # Integer digits fill the placeholders from the right; the leftmost
+    # one takes any digits left over
+    slots = [(position, char) for position, (tag, p) in enumerate(parts)
+             if tag == "integer" for char in p if char != ","]
+    filled = {position: "" for position, _ in slots}
+    digits = whole
+    count = 0
+    for n, (position, char) in enumerate(reversed(slots)):
+        if n == len(slots) - 1:
+            taken, digits = digits, ""
+        else:
+            taken, digits = digits[-1:], digits[:-1]
+        if not taken:
+            taken = {"0": "0", "?": " ", "#": ""}[char]
+        for digit in reversed(taken):
+            if digit != " ":
+                if grouping and count and count % 3 == 0:
+                    digit += ","
+                count += 1
+            filled[position] = digit + filled[position]
+
+    # Trailing zeros are dropped for # and blanked for ?
+    fraction = list(fraction)
+    for k in range(len(fractions) - 1, -1, -1):
+        if fraction[k] != "0" or fractions[k] == "0":
+            break
+        fraction[k] = "" if fractions[k] == "#" else " "
+
+    out = []
+    point = False
+    for position, (tag, payload) in enumerate(parts):
+        if tag == "text":
+            out.append(payload)
+        elif tag == "integer":
+            out.append(filled[position])
+        elif tag == "fraction":
+            if not point:
+                if not slots:
+                    out.append(whole)
+                out.append(".")
+                point = True
+            out.append("".join(fraction[: len(payload)]))
+            fraction = fraction[len(payload) :]
+        elif tag == "exponent":
+            if not slots and not point:
+                out.append(whole)
+            sign = "-" if magnitude < 0 else "+" if payload[0] == "+" else ""
+            out.append("E" + sign + str(abs(magnitude)).rjust(payload.count("0"), "0"))
+        elif tag == "general":
+            out.append(to_text(float(value)))
+    return "".join(out)"##,
+    },
+    Helper {
+        name: "format_date",
+        deps: &["ExcelError", "to_text"],
+        imports: &["import datetime", "import decimal"],
+        source: r##"def format_date(serial, parts):
+    """Formats an Excel date, the days since 1900 with the time of day as
+    the fraction, with the parts of a date section; see text_format."""
+    months = (
+        "January", "February", "March", "April", "May", "June", "July",
+        "August", "September", "October", "November", "December",
+    )
+    weekdays = (
+        "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday",
+    )
+    if not 0 <= serial < 2958466:
+        return ExcelError("#VALUE!")
+    places = max([p for tag, p in parts if tag == "subsecond"] or [0])
+    seconds = (decimal.Decimal(repr(serial)) * 86400).quantize(
+        decimal.Decimal(1).scaleb(-places), rounding=decimal.ROUND_HALF_UP
+    )
+    days, seconds = divmod(seconds, 86400)
+    days, whole = int(days), int(seconds)
+    # Excel's calendar has a 29 February 1900, so later dates are a day off
+    date = datetime.date(1899, 12, 31) + datetime.timedelta(days - (days > 60))
+    hour, minute, second = whole // 3600, whole // 60 % 60, whole % 60
+    elapsed = days * 86400 + whole
+    twelve_hour = any(tag == "ampm" for tag, _ in parts)
+
+    out = []
+    for tag, payload in parts:
+        if tag == "text":
+            out.append(payload)
+        elif tag == "year":
+            out.append(f"{date.year % 100:02}" if payload <= 2 else str(date.year))
+        elif tag == "month":
+            name = months[date.month - 1]
+            forms = (str(date.month), f"{date.month:02}", name[:3], name, name[0])
+            out.append(forms[min(payload, 5) - 1])
+        elif tag == "day":
+            name = weekdays[date.weekday()]
+            forms = (str(date.day), f"{date.day:02}", name[:3], name)
+            out.append(forms[min(payload, 4) - 1])
+        elif tag == "hour":
+            shown = (hour % 12 or 12) if twelve_hour else hour
+            out.append(str(shown).rjust(min(payload, 2), "0"))
+        elif tag == "minute":
+            out.append(str(minute).rjust(min(payload, 2), "0"))
+        elif tag == "second":
+            out.append(str(second).rjust(min(payload, 2), "0"))
+        elif tag == "subsecond":
+            out.append(format(seconds - whole, "f")[1:])
+        elif tag == "elapsed_hours":
+            out.append(str(elapsed // 3600).rjust(payload, "0"))
+        elif tag == "elapsed_minutes":
+            out.append(str(elapsed // 60).rjust(payload, "0"))
+        elif tag == "elapsed_seconds":
+            out.append(str(elapsed).rjust(payload, "0"))
+        elif tag == "ampm":
+            marker = "AM" if hour < 12 else "PM"
+            if payload.upper() == "A/P":
+                marker = marker[0]
+            out.append(marker if payload[0].isupper() else marker.lower())
+        elif tag == "general":
+            out.append(to_text(serial))
+    return "".join(out)"##,
+    },
+    Helper {
+        name: "text_format",
+        deps: &[
+            "ExcelError",
+            "to_text",
+            "parse_number",
+            "criterion",
+            "format_number",
+            "format_date",
+        ],
+        imports: &[],
+        source: r##"def text_format(value, sections, text_section=None):
+    """TEXT: value formatted with a number format parsed while transpiling.
+
+    sections are the format's sections for numbers as (condition, scale,
+    parts) tuples. condition is None or a pair like (">", 100), scale the
+    power of ten % and trailing commas apply, and parts the section's
+    (tag, payload) elements in order. text_section holds the parts of the
+    section for text, where ("value", None) stands for the text. Numeric
+    text is formatted as a number.
+    """
+    if isinstance(value, ExcelError):
+        return value
+    if value is None:
+        value = 0
+    if isinstance(value, str) and parse_number(value) is not None:
+        value = parse_number(value)
+    if isinstance(value, (str, bool)) or not sections:
+        if text_section is None:
+            return to_text(value)
+        return "".join(
+            to_text(value) if tag == "value" else payload
+            for tag, payload in text_section
+            if tag in ("text", "value")
+        )
+
+    # Without conditions, two sections split at zero and a third is for 0
+    explicit = any(condition is not None for condition, _, _ in sections)
+    defaults = [[None], [(">=", 0), ("<", 0)], [(">", 0), ("<", 0), None]]
+    for position, (condition, scale, parts) in enumerate(sections):
+        rule = condition if explicit else defaults[len(sections) - 1][position]
+        if rule is None or criterion(*rule)(value):
+            break
+    else:
+        return ExcelError("#VALUE!")
+    if any(tag not in ("text", "integer", "fraction", "exponent", "value", "general")
+           for tag, _ in parts):
+        return format_date(value, parts)
+    text = format_number(abs(value), scale, parts)
+    # The negative section shows its own sign, if any
+    if value < 0 and not (position == 1 and rule is not None and rule[0] in ("<", "<=")):
+        text = "-" + text
+    return text"##,
+    },
+    Helper {
+        name: "logicals",
+        deps: &["is_range", "flatten", "to_bool"],
+        imports: &[],
+        source: r##"def logicals(values):
+    """The TRUE/FALSE values AND, OR and XOR look at, or the first error.
+
+    Inside ranges text and blanks are skipped; other arguments are coerced
+    like IF's condition. #VALUE! when no logical value is left.
+    """
+    flags = []
+    for value in values:
+        cells = flatten(value) if is_range(value) else [value]
+        for cell in cells:
+            if isinstance(cell, ExcelError):
+                return cell
+            if cell is None or (is_range(value) and isinstance(cell, str)):
+                continue
+            flag = to_bool(cell)
+            if isinstance(flag, ExcelError):
+                return flag
+            flags.append(flag)
+    if not flags:
+        return ExcelError("#VALUE!")
+    return flags"##,
+    },
+    Helper {
+        name: "and_",
+        deps: &["logicals"],
+        imports: &[],
+        source: r##"def and_(*values):
+    """AND: whether every logical value is TRUE."""
+    flags = logicals(values)
+    if isinstance(flags, ExcelError):
+        return flags
+    return all(flags)"##,
+    },
+    Helper {
+        name: "or_",
+        deps: &["logicals"],
+        imports: &[],
+        source: r##"def or_(*values):
+    """OR: whether any logical value is TRUE."""
+    flags = logicals(values)
+    if isinstance(flags, ExcelError):
+        return flags
+    return any(flags)"##,
+    },
+    Helper {
+        name: "xor",
+        deps: &["logicals"],
+        imports: &[],
+        source: r##"def xor(*values):
+    """XOR: whether an odd number of logical values are TRUE."""
+    flags = logicals(values)
+    if isinstance(flags, ExcelError):
+        return flags
+    return sum(flags) % 2 == 1"##,
+    },
+    Helper {
+        name: "not_",
+        deps: &["to_bool"],
+        imports: &[],
+        source: r##"def not_(value):
+    """NOT: the opposite of a logical value."""
+    flag = to_bool(value)
+    if isinstance(flag, ExcelError):
+        return flag
+    return not flag"##,
+    },
+    Helper {
+        name: "switch",
+        deps: &["compare"],
+        imports: &[],
+        source: r##"def switch(value, *cases):
+    """SWITCH: the result paired with the first case equal to value, else
+    the default given as a trailing unpaired argument, else #N/A."""
+    if isinstance(value, ExcelError):
+        return value
+    for case, result in zip(cases[::2], cases[1::2]):
+        ordering = compare(value, case)
+        if isinstance(ordering, ExcelError):
+            return ordering
+        if ordering == 0:
+            return result
+    if len(cases) % 2:
+        return cases[-1]
+    return ExcelError("#N/A")"##,
+    },
+    Helper {
+        name: "iferror",
+        deps: &["ExcelError"],
+        imports: &[],
+        source: r##"def iferror(value, value_if_error):
+    """IFERROR: the fallback when value is any error."""
+    return value_if_error if isinstance(value, ExcelError) else value"##,
+    },
+    Helper {
+        name: "ifna",
+        deps: &["ExcelError"],
+        imports: &[],
+        source: r##"def ifna(value, value_if_na):
+    """IFNA: the fallback when value is #N/A; other errors pass through."""
+    if isinstance(value, ExcelError) and value.code == "#N/A":
+        return value_if_na
+    return value"##,
+    },
+    Helper {
+        name: "iserror",
+        deps: &["ExcelError"],
+        imports: &[],
+        source: r##"def iserror(value):
+    """ISERROR: whether value is any error."""
+    return isinstance(value, ExcelError)"##,
+    },
+    Helper {
+        name: "iserr",
+        deps: &["ExcelError"],
+        imports: &[],
+        source: r##"def iserr(value):
+    """ISERR: whether value is an error other than #N/A."""
+    return isinstance(value, ExcelError) and value.code != "#N/A""##,
+    },
+    Helper {
+        name: "isna",
+        deps: &["ExcelError"],
+        imports: &[],
+        source: r##"def isna(value):
+    """ISNA: whether value is the #N/A error."""
+    return isinstance(value, ExcelError) and value.code == "#N/A""##,
+    },
+    Helper {
+        name: "error_type",
+        deps: &["ExcelError"],
+        imports: &[],
+        source: r##"def error_type(value):
+    """ERROR.TYPE: the error's number (1 for #NULL!, 7 for #N/A, ...),
+    or #N/A when value is not an error."""
+    if isinstance(value, ExcelError) and value.code in ExcelError.TYPES:
+        return ExcelError.TYPES.index(value.code) + 1
+    return ExcelError("#N/A")"##,
+    },
+];
+
+/// Looks up a helper by name
+pub fn helper(name: &str) -> Option<&'static Helper> {
+    HELPERS.iter().find(|helper| helper.name == name)
+}
+
+/// Expands `names` with their dependencies, in [`HELPERS`] order
+pub fn resolve<'a>(names: impl IntoIterator<Item = &'a str>) -> Vec<&'static Helper> {
+    let mut needed = std::collections::HashSet::new();
+    let mut pending: Vec<&str> = names.into_iter().collect();
+    while let Some(name) = pending.pop() {
+        if let Some(helper) = helper(name)
+            && needed.insert(helper.name)
+        {
+            pending.extend(helper.deps.iter().copied());
+        }
+    }
+    HELPERS
+        .iter()
+        .filter(|helper| needed.contains(helper.name))
+        .collect()
+}
+
+/// The distinct imports `helpers` need, sorted
+pub fn imports<'a>(helpers: impl IntoIterator<Item = &'a Helper>) -> Vec<&'static str> {
+    let imports: std::collections::BTreeSet<&'static str> = helpers
+        .into_iter()
+        .flat_map(|helper| helper.imports.iter().copied())
+        .collect();
+    imports.into_iter().collect()
+}
+
+/// Source of the complete `expy_runtime.py` module
+pub fn module() -> String {
+    let names: Vec<String> = HELPERS
+        .iter()
+        .map(|helper| format!("    \"{}\",", helper.name))
+        .collect();
+    let mut out = format!(
+        "\"\"\"Runtime support for Python code generated by expy {VERSION}.\n\n\
+         Generated file, do not edit. Regenerate it whenever expy is upgraded.\n\
+         \"\"\"\n\n\
+         {}\n\n\
+         __version__ = \"{VERSION}\"\n\n\
+         __all__ = [\n{}\n]\n",
+        imports(HELPERS).join("\n"),
+        names.join("\n")
+    );
+    for helper in HELPERS {
+        out.push_str("\n\n");
+        out.push_str(helper.source);
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_helper_names_unique() {
+        let mut names: Vec<&str> = HELPERS.iter().map(|helper| helper.name).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), HELPERS.len());
+    }
+
+    #[test]
+    fn test_deps_exist_and_come_first() {
+        for (index, helper) in HELPERS.iter().enumerate() {
+            for dep in helper.deps {
+                let position = HELPERS.iter().position(|h| h.name == *dep);
+                assert!(position.is_some_and(|p| p < index), "{dep}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_sources_only_use_their_deps() {
+        for helper in HELPERS {
+            let available: Vec<&str> = resolve([helper.name]).iter().map(|h| h.name).collect();
+            for other in HELPERS {
+                let uses = helper.source.match_indices(other.name).any(|(i, _)| {
+                    let before = helper.source[..i].chars().next_back();
+                    let after = helper.source[i + other.name.len()..].chars().next();
+                    !before.is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '.')
+                        && matches!(after, Some('(' | ')' | ','))
+                });
+                assert!(
+                    !uses || available.contains(&other.name),
+                    "{} uses {}",
+                    helper.name,
+                    other.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_resolve_includes_deps() {
+        let names: Vec<&str> = resolve(["concat"]).iter().map(|h| h.name).collect();
+        assert_eq!(names, ["ExcelError", "first_error", "to_text", "concat"]);
+    }
+
+    #[test]
+    fn test_module_is_versioned() {
+        let module = module();
+        assert!(module.contains(&format!("__version__ = \"{VERSION}\"")));
+        assert!(module.ends_with("\n"));
+    }
+
+    #[test]
+    fn test_module_imports_come_first() {
+        let module = module();
+        let version = module.find("__version__").unwrap();
+        for import in imports(HELPERS) {
+            assert!(module.find(import).is_some_and(|i| i < version), "{import}");
+        }
+        assert_eq!(
+            imports(HELPERS),
+            [
+                "import datetime",
+                "import decimal",
+                "import math",
+                "import operator",
+                "import re"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_module_contains_every_helper() {
+        let module = module();
+        for helper in HELPERS {
+            assert!(module.contains(helper.source), "{}", helper.name);
+            assert!(module.contains(&format!("    \"{}\",", helper.name)));
+        }
+    }
+
+    #[test]
+    fn test_sources_define_their_name() {
+        for helper in HELPERS {
+            let function = format!("def {}(", helper.name);
+            let class = format!("class {}:", helper.name);
+            assert!(
+                helper.source.starts_with(&function) || helper.source.starts_with(&class),
+                "{}",
+                helper.name
+            );
+        }
+    }
+}
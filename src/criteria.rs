@@ -0,0 +1,221 @@
+//! The criteria language of SUMIF, COUNTIFS and the other conditional
+//! aggregates: `">=10"`, `"<>"`, `"apple"`, `"a*b?"`, `"~*"`.
+//!
+//! A criterion is an optional comparison operator followed by an operand.
+//! The code generator parses criteria written as string literals while
+//! transpiling, so the generated code calls the runtime's `criterion`
+//! helper directly; other criteria go through `parse_criteria` at run time,
+//! which follows the same rules as [`Criterion::parse`].
+
+/// Error values a criterion can name, as in the runtime's `ExcelError.TYPES`
+const ERROR_CODES: &[&str] = &[
+    "#NULL!",
+    "#DIV/0!",
+    "#VALUE!",
+    "#REF!",
+    "#NAME?",
+    "#NUM!",
+    "#N/A",
+    "#GETTING_DATA",
+    "#SPILL!",
+    "#CONNECT!",
+    "#BLOCKED!",
+    "#UNKNOWN!",
+    "#FIELD!",
+    "#CALC!",
+];
+
+/// How a cell is compared with the operand
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Equal,
+    NotEqual,
+    Less,
+    Greater,
+    LessEqual,
+    GreaterEqual,
+}
+
+impl Operator {
+    /// Operators by prefix, longest first so `<=` wins over `<`
+    const PREFIXES: [(&'static str, Operator); 6] = [
+        ("<=", Self::LessEqual),
+        (">=", Self::GreaterEqual),
+        ("<>", Self::NotEqual),
+        ("<", Self::Less),
+        (">", Self::Greater),
+        ("=", Self::Equal),
+    ];
+
+    /// The operator as written in a criterion
+    pub fn symbol(&self) -> &'static str {
+        Self::PREFIXES
+            .iter()
+            .find(|(_, op)| op == self)
+            .map(|(symbol, _)| *symbol)
+            .unwrap()
+    }
+}
+
+/// What cells are compared against
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    /// Nothing after `=` or `<>`: `"="` matches only blank cells
+    Blank,
+    Number(f64),
+    Bool(bool),
+    Error(String),
+    /// Compared case-insensitively. With `=` and `<>`, `*` matches any run
+    /// of characters, `?` any one character and `~` escapes the next one.
+    Text(String),
+}
+
+/// A parsed criterion
+#[derive(Debug, Clone, PartialEq)]
+pub struct Criterion {
+    pub op: Operator,
+    pub operand: Operand,
+}
+
+impl Criterion {
+    /// Parses a criterion given as text. Every string is a valid
+    /// criterion: text that is not a number, boolean or error is matched
+    /// as text, and no operator means `=`.
+    pub fn parse(criteria: &str) -> Self {
+        let prefix = Operator::PREFIXES
+            .iter()
+            .find(|(symbol, _)| criteria.starts_with(symbol));
+        let (op, text) = match prefix {
+            Some((symbol, op)) => (*op, &criteria[symbol.len()..]),
+            None => (Operator::Equal, criteria),
+        };
+
+        let operand = if text.is_empty() && prefix.is_some() {
+            Operand::Blank
+        } else if let Some(n) = parse_number(text) {
+            Operand::Number(n)
+        } else if text.eq_ignore_ascii_case("TRUE") || text.eq_ignore_ascii_case("FALSE") {
+            Operand::Bool(text.eq_ignore_ascii_case("TRUE"))
+        } else if let Some(code) = ERROR_CODES
+            .iter()
+            .find(|code| code.eq_ignore_ascii_case(text))
+        {
+            Operand::Error(code.to_string())
+        } else {
+            Operand::Text(text.to_string())
+        };
+        Self { op, operand }
+    }
+
+    /// Whether the operand is a pattern rather than plain text
+    pub fn uses_wildcards(&self) -> bool {
+        matches!(self.op, Operator::Equal | Operator::NotEqual)
+            && matches!(&self.operand, Operand::Text(text) if text.contains(['*', '?', '~']))
+    }
+}
+
+/// Reads text that Excel treats as a number in criteria: an optionally
+/// signed decimal with an optional exponent, allowing surrounding spaces
+pub fn parse_number(text: &str) -> Option<f64> {
+    let text = text.trim_matches(' ');
+    let unsigned = text.strip_prefix(['+', '-']).unwrap_or(text);
+    let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+        Some(index) => (&unsigned[..index], Some(&unsigned[index + 1..])),
+        None => (unsigned, None),
+    };
+
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    let mantissa_ok =
+        digits(whole) && digits(fraction) && !(whole.is_empty() && fraction.is_empty());
+    let exponent_ok = exponent.is_none_or(|exponent| {
+        let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+        !exponent.is_empty() && digits(exponent)
+    });
+    if !mantissa_ok || !exponent_ok {
+        return None;
+    }
+    text.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(criteria: &str) -> (Operator, Operand) {
+        let criterion = Criterion::parse(criteria);
+        (criterion.op, criterion.operand)
+    }
+
+    #[test]
+    fn test_operators() {
+        assert_eq!(
+            parse(">=10"),
+            (Operator::GreaterEqual, Operand::Number(10.0))
+        );
+        assert_eq!(parse("<=10"), (Operator::LessEqual, Operand::Number(10.0)));
+        assert_eq!(parse("<>10"), (Operator::NotEqual, Operand::Number(10.0)));
+        assert_eq!(parse("<10"), (Operator::Less, Operand::Number(10.0)));
+        assert_eq!(parse(">10"), (Operator::Greater, Operand::Number(10.0)));
+        assert_eq!(parse("=10"), (Operator::Equal, Operand::Number(10.0)));
+        assert_eq!(parse("10"), (Operator::Equal, Operand::Number(10.0)));
+    }
+
+    #[test]
+    fn test_symbol_round_trips() {
+        for (symbol, op) in Operator::PREFIXES {
+            assert_eq!(op.symbol(), symbol);
+        }
+    }
+
+    #[test]
+    fn test_blank_operands() {
+        assert_eq!(parse("="), (Operator::Equal, Operand::Blank));
+        assert_eq!(parse("<>"), (Operator::NotEqual, Operand::Blank));
+        // No operator: blanks and empty text
+        assert_eq!(parse(""), (Operator::Equal, Operand::Text(String::new())));
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(parse("-2.5").1, Operand::Number(-2.5));
+        assert_eq!(parse(">.5").1, Operand::Number(0.5));
+        assert_eq!(parse("<1e3").1, Operand::Number(1000.0));
+        assert_eq!(parse("= 7 ").1, Operand::Number(7.0));
+        assert_eq!(parse("1e").1, Operand::Text("1e".into()));
+        assert_eq!(parse("inf").1, Operand::Text("inf".into()));
+        assert_eq!(parse(".").1, Operand::Text(".".into()));
+        assert_eq!(parse("1,000").1, Operand::Text("1,000".into()));
+    }
+
+    #[test]
+    fn test_booleans_and_errors() {
+        assert_eq!(parse("true").1, Operand::Bool(true));
+        assert_eq!(parse("<>FALSE").1, Operand::Bool(false));
+        assert_eq!(parse("#n/a").1, Operand::Error("#N/A".into()));
+        assert_eq!(parse("=#DIV/0!").1, Operand::Error("#DIV/0!".into()));
+        assert_eq!(parse("#DIV/0").1, Operand::Text("#DIV/0".into()));
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(
+            parse("apple"),
+            (Operator::Equal, Operand::Text("apple".into()))
+        );
+        // Only the first operator counts; the rest is the operand
+        assert_eq!(parse("=<5"), (Operator::Equal, Operand::Text("<5".into())));
+        assert_eq!(
+            parse(">=b"),
+            (Operator::GreaterEqual, Operand::Text("b".into()))
+        );
+    }
+
+    #[test]
+    fn test_wildcards() {
+        assert!(Criterion::parse("a*b?").uses_wildcards());
+        assert!(Criterion::parse("<>~*").uses_wildcards());
+        assert!(!Criterion::parse(">a*").uses_wildcards());
+        assert!(!Criterion::parse("apple").uses_wildcards());
+    }
+}
@@ -0,0 +1,589 @@
+//! Excel number format codes, as used by TEXT: `"$#,##0.00"`,
+//! `"0.0%;[Red]-0.0%"`, `"yyyy-mm-dd hh:mm"`, `"0.00E+00"`.
+//!
+//! A format has up to four sections separated by `;`: positive numbers,
+//! negative numbers, zero and text, unless brackets such as `[>100]` give
+//! the numeric sections their own conditions. The code generator parses
+//! formats written as string literals while transpiling and passes the
+//! parsed sections to the runtime's `text_format` helper.
+
+use std::fmt;
+
+use crate::criteria::{Criterion, Operand, Operator};
+
+/// Color names allowed in brackets, besides `[Color1]` to `[Color56]`
+const COLORS: &[&str] = &[
+    "Black", "Blue", "Cyan", "Green", "Magenta", "Red", "White", "Yellow",
+];
+
+/// Why a format code cannot be used; TEXT gives #VALUE! for these
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatError {
+    UnclosedQuote,
+    UnclosedBracket,
+    UnknownBracket(String),
+    TooManySections,
+    /// Fractions such as `# ?/?`, which are valid but not supported yet
+    Fraction,
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnclosedQuote => write!(f, "unclosed quote"),
+            Self::UnclosedBracket => write!(f, "unclosed bracket"),
+            Self::UnknownBracket(text) => write!(f, "unknown bracket [{text}]"),
+            Self::TooManySections => write!(f, "more than 4 sections"),
+            Self::Fraction => write!(f, "fractions are not supported"),
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+/// One element of a section, in display order
+#[derive(Debug, Clone, PartialEq)]
+pub enum Part {
+    /// Shown as is: quoted text, `\x` escapes, `$`, `-`, spaces and the like
+    Text(String),
+    /// Digit placeholders before the decimal point: `0` pads with zeros,
+    /// `?` with spaces and `#` with nothing. A `,` among them groups
+    /// thousands.
+    Integer(String),
+    /// Digit placeholders after the decimal point; the first fraction
+    /// part is preceded by the point itself
+    Fraction(String),
+    /// `E+` or `E-` and the exponent's placeholders; `+` also shows the
+    /// sign of positive exponents
+    Exponent {
+        plus: bool,
+        digits: String,
+    },
+    /// `@`: the text being formatted
+    Value,
+    /// `General`: the number the way Excel shows it unformatted
+    General,
+    /// `yy` or `yyyy`, by the number of letters
+    Year(usize),
+    /// `m` to `mmmmm`: number, padded number, short name, name, initial
+    Month(usize),
+    /// `d` to `dddd`: number, padded number, short weekday, weekday
+    Day(usize),
+    Hour(usize),
+    /// `m` or `mm` right after hours or right before seconds
+    Minute(usize),
+    Second(usize),
+    /// Decimal places of the seconds, as in `ss.00`
+    Subsecond(usize),
+    /// `[h]`: hours in total rather than of the day
+    ElapsedHours(usize),
+    ElapsedMinutes(usize),
+    ElapsedSeconds(usize),
+    /// `AM/PM` or `A/P` as written, which switches hours to 12-hour time
+    AmPm(String),
+}
+
+impl Part {
+    fn is_date(&self) -> bool {
+        matches!(
+            self,
+            Self::Year(_)
+                | Self::Month(_)
+                | Self::Day(_)
+                | Self::Hour(_)
+                | Self::Minute(_)
+                | Self::Second(_)
+                | Self::Subsecond(_)
+                | Self::ElapsedHours(_)
+                | Self::ElapsedMinutes(_)
+                | Self::ElapsedSeconds(_)
+                | Self::AmPm(_)
+        )
+    }
+}
+
+/// A `[>100]` style condition choosing the numbers a section formats
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    pub op: Operator,
+    pub value: f64,
+}
+
+/// One `;`-separated section of a format
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Section {
+    pub condition: Option<Condition>,
+    /// `[Red]` and the like; TEXT returns plain text, so it is not shown
+    pub color: Option<String>,
+    /// Power of ten the number is scaled by: +2 for each `%`, -3 for each
+    /// `,` after the last digit placeholder
+    pub scale: i32,
+    pub parts: Vec<Part>,
+}
+
+impl Section {
+    /// Whether the section shows a date or time
+    pub fn is_date(&self) -> bool {
+        self.parts.iter().any(Part::is_date)
+    }
+}
+
+/// A parsed format code
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberFormat {
+    /// Sections for numbers, in order; empty when the format only has a
+    /// text section such as `"@"`
+    pub numbers: Vec<Section>,
+    /// The section for text: the fourth, or a last one containing `@`
+    pub text: Option<Section>,
+}
+
+impl NumberFormat {
+    pub fn parse(code: &str) -> Result<Self, FormatError> {
+        let mut numbers = split_sections(code)?
+            .iter()
+            .map(|section| parse_section(section))
+            .collect::<Result<Vec<_>, _>>()?;
+        if numbers.len() > 4 {
+            return Err(FormatError::TooManySections);
+        }
+        let text = match numbers.last() {
+            Some(_) if numbers.len() == 4 => numbers.pop(),
+            Some(last) if last.parts.contains(&Part::Value) => numbers.pop(),
+            _ => None,
+        };
+        Ok(Self { numbers, text })
+    }
+}
+
+/// Splits a format at the `;`s outside quotes, brackets and escapes
+fn split_sections(code: &str) -> Result<Vec<String>, FormatError> {
+    let mut sections = vec![String::new()];
+    let mut chars = code.chars();
+    while let Some(c) = chars.next() {
+        let section = sections.last_mut().unwrap();
+        match c {
+            ';' => {
+                sections.push(String::new());
+                continue;
+            }
+            '"' => {
+                section.push(c);
+                loop {
+                    let c = chars.next().ok_or(FormatError::UnclosedQuote)?;
+                    section.push(c);
+                    if c == '"' {
+                        break;
+                    }
+                }
+            }
+            '[' => {
+                section.push(c);
+                loop {
+                    let c = chars.next().ok_or(FormatError::UnclosedBracket)?;
+                    section.push(c);
+                    if c == ']' {
+                        break;
+                    }
+                }
+            }
+            '\\' | '_' | '*' => {
+                section.push(c);
+                section.extend(chars.next());
+            }
+            c => section.push(c),
+        }
+    }
+    Ok(sections)
+}
+
+fn parse_section(code: &str) -> Result<Section, FormatError> {
+    let chars: Vec<char> = code.chars().collect();
+    let placeholder = |c: &char| matches!(c, '0' | '#' | '?');
+    let numeric = chars.iter().any(placeholder);
+    let mut section = Section::default();
+    let mut parts = Vec::new();
+    let mut point = false;
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let rest = &chars[i..];
+        let run = chars[i..]
+            .iter()
+            .take_while(|next| next.eq_ignore_ascii_case(&c))
+            .count();
+        i += 1;
+        match c.to_ascii_lowercase() {
+            '"' => {
+                let end = chars[i..].iter().position(|&c| c == '"').unwrap();
+                push_text(&mut parts, &chars[i..i + end].iter().collect::<String>());
+                i += end + 1;
+            }
+            '\\' => {
+                if let Some(next) = chars.get(i) {
+                    push_text(&mut parts, &next.to_string());
+                    i += 1;
+                }
+            }
+            '_' => {
+                push_text(&mut parts, " ");
+                i += 1;
+            }
+            '*' => i += 1,
+            '[' => {
+                let end = chars[i..].iter().position(|&c| c == ']').unwrap();
+                let bracket: String = chars[i..i + end].iter().collect();
+                i += end + 1;
+                parse_bracket(&bracket, &mut section, &mut parts)?;
+            }
+            '0' | '#' | '?' => match parts.last_mut() {
+                Some(Part::Integer(digits) | Part::Fraction(digits))
+                | Some(Part::Exponent { digits, .. }) => digits.push(c),
+                _ if point => parts.push(Part::Fraction(c.to_string())),
+                _ => parts.push(Part::Integer(c.to_string())),
+            },
+            ',' if matches!(parts.last(), Some(Part::Integer(_)))
+                && chars.get(i).is_some_and(placeholder) =>
+            {
+                if let Some(Part::Integer(digits)) = parts.last_mut() {
+                    digits.push(',');
+                }
+            }
+            ',' if matches!(parts.last(), Some(Part::Integer(_) | Part::Fraction(_)))
+                && !chars[i..].iter().any(placeholder) =>
+            {
+                section.scale -= 3;
+            }
+            '.' if matches!(parts.last(), Some(Part::Second(_))) && chars.get(i) == Some(&'0') => {
+                let zeros = chars[i..].iter().take_while(|&&c| c == '0').count();
+                parts.push(Part::Subsecond(zeros));
+                i += zeros;
+            }
+            '.' if numeric && !point => {
+                point = true;
+                parts.push(Part::Fraction(String::new()));
+            }
+            'e' if numeric && matches!(chars.get(i), Some('+' | '-')) => {
+                parts.push(Part::Exponent {
+                    plus: chars[i] == '+',
+                    digits: String::new(),
+                });
+                i += 1;
+            }
+            '/' if matches!(parts.last(), Some(Part::Integer(_)))
+                && chars
+                    .get(i)
+                    .is_some_and(|c| placeholder(c) || c.is_ascii_digit()) =>
+            {
+                return Err(FormatError::Fraction);
+            }
+            '%' => {
+                section.scale += 2;
+                push_text(&mut parts, "%");
+            }
+            '@' => parts.push(Part::Value),
+            'g' if starts_with_ignore_case(rest, "General") => {
+                parts.push(Part::General);
+                i += 6;
+            }
+            'a' if starts_with_ignore_case(rest, "AM/PM") => {
+                parts.push(Part::AmPm(rest[..5].iter().collect()));
+                i += 4;
+            }
+            'a' if starts_with_ignore_case(rest, "A/P") => {
+                parts.push(Part::AmPm(rest[..3].iter().collect()));
+                i += 2;
+            }
+            'y' | 'm' | 'd' | 'h' | 's' => {
+                parts.push(match c.to_ascii_lowercase() {
+                    'y' => Part::Year(run),
+                    'm' => Part::Month(run),
+                    'd' => Part::Day(run),
+                    'h' => Part::Hour(run),
+                    _ => Part::Second(run),
+                });
+                i += run - 1;
+            }
+            _ => push_text(&mut parts, &c.to_string()),
+        }
+    }
+
+    resolve_minutes(&mut parts);
+    section.parts = parts;
+    Ok(section)
+}
+
+/// Applies a bracketed code: a condition, a color, a currency symbol or
+/// an elapsed time unit
+fn parse_bracket(
+    bracket: &str,
+    section: &mut Section,
+    parts: &mut Vec<Part>,
+) -> Result<(), FormatError> {
+    let unit = bracket.chars().next().map(|c| c.to_ascii_lowercase());
+    let one_unit = unit.is_some_and(|unit| bracket.chars().all(|c| c.eq_ignore_ascii_case(&unit)));
+    if let Some(currency) = bracket.strip_prefix('$') {
+        // [$€-407] shows €; [$-409] only picks a locale
+        let symbol = currency.split('-').next().unwrap_or_default();
+        if !symbol.is_empty() {
+            push_text(parts, symbol);
+        }
+    } else if one_unit && matches!(unit, Some('h' | 'm' | 's')) {
+        let width = bracket.len();
+        parts.push(match unit {
+            Some('h') => Part::ElapsedHours(width),
+            Some('m') => Part::ElapsedMinutes(width),
+            _ => Part::ElapsedSeconds(width),
+        });
+    } else if COLORS
+        .iter()
+        .any(|color| color.eq_ignore_ascii_case(bracket))
+        || bracket
+            .get(..5)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("Color"))
+            && bracket[5..]
+                .parse::<u8>()
+                .is_ok_and(|n| (1..=56).contains(&n))
+    {
+        section.color = Some(bracket.to_string());
+    } else if let Criterion {
+        op,
+        operand: Operand::Number(value),
+    } = Criterion::parse(bracket)
+        && bracket.starts_with(['<', '>', '='])
+    {
+        section.condition = Some(Condition { op, value });
+    } else {
+        return Err(FormatError::UnknownBracket(bracket.to_string()));
+    }
+    Ok(())
+}
+
+fn starts_with_ignore_case(chars: &[char], prefix: &str) -> bool {
+    chars.len() >= prefix.len()
+        && prefix
+            .chars()
+            .zip(chars)
+            .all(|(expected, c)| c.eq_ignore_ascii_case(&expected))
+}
+
+/// Appends text, merging it with text right before it
+fn push_text(parts: &mut Vec<Part>, text: &str) {
+    match parts.last_mut() {
+        Some(Part::Text(last)) => last.push_str(text),
+        _ => parts.push(Part::Text(text.to_string())),
+    }
+}
+
+/// `m` and `mm` are minutes right after hours or right before seconds,
+/// looking past any text between them
+fn resolve_minutes(parts: &mut [Part]) {
+    let dates: Vec<usize> = (0..parts.len()).filter(|&i| parts[i].is_date()).collect();
+    for (n, &index) in dates.iter().enumerate() {
+        let Part::Month(count @ (1 | 2)) = parts[index] else {
+            continue;
+        };
+        let after_hours =
+            n > 0 && matches!(parts[dates[n - 1]], Part::Hour(_) | Part::ElapsedHours(_));
+        let before_seconds = dates
+            .get(n + 1)
+            .is_some_and(|&next| matches!(parts[next], Part::Second(_) | Part::ElapsedSeconds(_)));
+        if after_hours || before_seconds {
+            parts[index] = Part::Minute(count);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts(code: &str) -> Vec<Part> {
+        let format = NumberFormat::parse(code).unwrap();
+        format.numbers[0].parts.clone()
+    }
+
+    fn text(text: &str) -> Part {
+        Part::Text(text.to_string())
+    }
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(
+            parts("$#,##0.00"),
+            [
+                text("$"),
+                Part::Integer("#,##0".into()),
+                Part::Fraction("00".into())
+            ]
+        );
+        assert_eq!(
+            parts("000-0000"),
+            [
+                Part::Integer("000".into()),
+                text("-"),
+                Part::Integer("0000".into())
+            ]
+        );
+        assert_eq!(
+            parts("0."),
+            [Part::Integer("0".into()), Part::Fraction("".into())]
+        );
+    }
+
+    #[test]
+    fn test_scaling() {
+        let format = NumberFormat::parse("0.0%").unwrap();
+        assert_eq!(format.numbers[0].scale, 2);
+        let format = NumberFormat::parse("#,##0,,\"M\"").unwrap();
+        assert_eq!(format.numbers[0].scale, -6);
+        assert_eq!(
+            format.numbers[0].parts,
+            [Part::Integer("#,##0".into()), text("M")]
+        );
+    }
+
+    #[test]
+    fn test_scientific() {
+        assert_eq!(
+            parts("0.00E+00"),
+            [
+                Part::Integer("0".into()),
+                Part::Fraction("00".into()),
+                Part::Exponent {
+                    plus: true,
+                    digits: "00".into()
+                }
+            ]
+        );
+        // Without placeholders E is just a letter
+        assert_eq!(parts("\"x\"E+"), [text("xE+")]);
+    }
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(
+            parts("\"Total: \"0\\!_)"),
+            [text("Total: "), Part::Integer("0".into()), text("! ")]
+        );
+        assert_eq!(parts("*-0"), [Part::Integer("0".into())]);
+        assert_eq!(parts("[$€-407]0"), [text("€"), Part::Integer("0".into())]);
+    }
+
+    #[test]
+    fn test_dates() {
+        assert_eq!(
+            parts("yyyy-mm-dd"),
+            [
+                Part::Year(4),
+                text("-"),
+                Part::Month(2),
+                text("-"),
+                Part::Day(2)
+            ]
+        );
+        assert_eq!(
+            parts("dddd, mmmm d"),
+            [
+                Part::Day(4),
+                text(", "),
+                Part::Month(4),
+                text(" "),
+                Part::Day(1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_minutes() {
+        assert_eq!(
+            parts("h:mm AM/PM"),
+            [
+                Part::Hour(1),
+                text(":"),
+                Part::Minute(2),
+                text(" "),
+                Part::AmPm("AM/PM".into())
+            ]
+        );
+        assert_eq!(
+            parts("[h]:mm:ss.00"),
+            [
+                Part::ElapsedHours(1),
+                text(":"),
+                Part::Minute(2),
+                text(":"),
+                Part::Second(2),
+                Part::Subsecond(2)
+            ]
+        );
+        assert_eq!(parts("m:s")[0], Part::Minute(1));
+        assert_eq!(parts("m/d")[0], Part::Month(1));
+    }
+
+    #[test]
+    fn test_sections() {
+        let format = NumberFormat::parse("0.00;[Red]-0.00;\"zero\";\"text: \"@").unwrap();
+        assert_eq!(format.numbers.len(), 3);
+        assert_eq!(format.numbers[1].color.as_deref(), Some("Red"));
+        assert_eq!(format.text.unwrap().parts, [text("text: "), Part::Value]);
+
+        let format = NumberFormat::parse("0;@").unwrap();
+        assert_eq!(format.numbers.len(), 1);
+        assert!(format.text.is_some());
+        assert!(NumberFormat::parse("@").unwrap().numbers.is_empty());
+        // A semicolon inside quotes does not split
+        assert_eq!(NumberFormat::parse("\"a;b\"0").unwrap().numbers.len(), 1);
+    }
+
+    #[test]
+    fn test_conditions() {
+        let format = NumberFormat::parse("[>=100][Blue]0;[<-5]0;0").unwrap();
+        assert_eq!(
+            format.numbers[0].condition,
+            Some(Condition {
+                op: Operator::GreaterEqual,
+                value: 100.0
+            })
+        );
+        assert_eq!(format.numbers[0].color.as_deref(), Some("Blue"));
+        assert_eq!(
+            format.numbers[1].condition,
+            Some(Condition {
+                op: Operator::Less,
+                value: -5.0
+            })
+        );
+        assert_eq!(format.numbers[2].condition, None);
+    }
+
+    #[test]
+    fn test_general() {
+        assert_eq!(parts("General"), [Part::General]);
+        assert_eq!(parts("general\" units\""), [Part::General, text(" units")]);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            NumberFormat::parse("\"abc"),
+            Err(FormatError::UnclosedQuote)
+        );
+        assert_eq!(
+            NumberFormat::parse("[Red"),
+            Err(FormatError::UnclosedBracket)
+        );
+        assert_eq!(
+            NumberFormat::parse("[Mauve]0"),
+            Err(FormatError::UnknownBracket("Mauve".into()))
+        );
+        assert_eq!(
+            NumberFormat::parse("0;0;0;@;0"),
+            Err(FormatError::TooManySections)
+        );
+        assert_eq!(NumberFormat::parse("# ?/?"), Err(FormatError::Fraction));
+        assert_eq!(NumberFormat::parse("0/8"), Err(FormatError::Fraction));
+        assert!(NumberFormat::parse("m/d/yyyy").is_ok());
+    }
+}
@@ -0,0 +1,955 @@
+//! Catalog of the Excel functions expy can translate.
+//!
+//! The lexer recognizes every built-in name (see
+//! [`crate::bindings::builtins`]); this catalog covers the subset with a
+//! Python translation and describes how each may be called. The parser
+//! checks argument counts against it and the code generator looks up the
+//! translation here.
+
+use wasm_bindgen::prelude::*;
+
+/// Largest number of arguments Excel allows in a call
+pub const MAX_ARGS: usize = 255;
+
+/// Grouping used by Excel's function library
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Information,
+    Logical,
+    Math,
+    Statistical,
+    Lookup,
+    Text,
+}
+
+/// What a function argument accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arg {
+    /// A single value
+    Value,
+    /// A single value read as text; left empty, as in `TEXTSPLIT(A1,,",")`,
+    /// it is `""` rather than 0
+    Text,
+    /// A value, a range or an array constant, e.g. SUM's numbers
+    Range,
+    /// A reference to cells, e.g. SUMIF's range; constants are rejected
+    Reference,
+    /// A condition such as `">=10"` in the [`crate::criteria`] language
+    Criteria,
+}
+
+/// What a function evaluates to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReturnType {
+    Number,
+    Text,
+    Bool,
+    /// Depends on the arguments, e.g. IFERROR
+    Any,
+    /// A 2D array that spills, e.g. TEXTSPLIT
+    Array,
+    /// A reference usable as a range end, e.g. INDEX
+    Reference,
+}
+
+/// How a call is written in Python
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Translation {
+    /// A call to the runtime helper of this name with the arguments in order
+    Helper(&'static str),
+    /// Dedicated code in the emitter, e.g. IF as a conditional expression;
+    /// the text sketches the Python it becomes
+    Custom(&'static str),
+}
+
+/// Metadata for one supported function
+#[derive(Debug)]
+pub struct Function {
+    pub name: &'static str,
+    pub category: Category,
+    pub min_args: usize,
+    /// `None` when the function takes up to [`MAX_ARGS`] arguments
+    pub max_args: Option<usize>,
+    /// One entry per parameter
+    pub args: &'static [Arg],
+    /// How many trailing parameters repeat together past `args`: 1 for
+    /// SUM's numbers, 2 for IFS's condition/value pairs, 0 for none
+    pub repeat: usize,
+    /// Recalculated on every change, like NOW or RAND
+    pub volatile: bool,
+    pub returns: ReturnType,
+    pub python: Translation,
+    pub summary: &'static str,
+}
+
+impl Function {
+    /// Whether a call with `count` arguments is valid
+    pub fn accepts(&self, count: usize) -> bool {
+        count >= self.min_args
+            && count <= self.max_args.unwrap_or(MAX_ARGS)
+            && (count - self.min_args).is_multiple_of(self.repeat.max(1))
+    }
+
+    /// What the argument at `index` (0-based) accepts
+    pub fn arg(&self, index: usize) -> Option<Arg> {
+        if index >= self.max_args.unwrap_or(MAX_ARGS) {
+            return None;
+        }
+        if index < self.args.len() {
+            return Some(self.args[index]);
+        }
+        let first = self.args.len().checked_sub(self.repeat)?;
+        self.args
+            .get(first + (index - first) % self.repeat.max(1))
+            .copied()
+    }
+}
+
+/// Describes an argument count for messages: `1 argument`, `2-3 arguments`,
+/// `at least 1 argument`, or `2, 4, 6, ... arguments` for groups of `repeat`
+pub fn describe_arity(min: usize, max: Option<usize>, repeat: usize) -> String {
+    if repeat > 1 {
+        return format!(
+            "{min}, {}, {}, ... arguments",
+            min + repeat,
+            min + 2 * repeat
+        );
+    }
+    let (count, last) = match max {
+        Some(max) if max == min => (min.to_string(), min),
+        Some(max) => (format!("{min}-{max}"), max),
+        None => (format!("at least {min}"), min),
+    };
+    format!("{count} argument{}", if last == 1 { "" } else { "s" })
+}
+
+/// Supported functions, sorted by name for binary search
+pub const FUNCTIONS: &[Function] = &[
+    Function {
+        name: "ABS",
+        category: Category::Math,
+        min_args: 1,
+        max_args: Some(1),
+        args: &[Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("abs_"),
+        summary: "Absolute value of a number",
+    },
+    Function {
+        name: "AND",
+        category: Category::Logical,
+        min_args: 1,
+        max_args: None,
+        args: &[Arg::Range],
+        repeat: 1,
+        volatile: false,
+        returns: ReturnType::Bool,
+        python: Translation::Helper("and_"),
+        summary: "Whether every logical value is TRUE, skipping text and blanks in ranges",
+    },
+    Function {
+        name: "AVERAGEIF",
+        category: Category::Statistical,
+        min_args: 2,
+        max_args: Some(3),
+        args: &[Arg::Reference, Arg::Criteria, Arg::Reference],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("averageif"),
+        summary: "Average of the cells matching a criterion",
+    },
+    Function {
+        name: "AVERAGEIFS",
+        category: Category::Statistical,
+        min_args: 3,
+        max_args: None,
+        args: &[Arg::Reference, Arg::Reference, Arg::Criteria],
+        repeat: 2,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("averageifs"),
+        summary: "Average of the cells matching every criterion",
+    },
+    Function {
+        name: "CEILING.MATH",
+        category: Category::Math,
+        min_args: 1,
+        max_args: Some(3),
+        args: &[Arg::Value, Arg::Value, Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("ceiling_math"),
+        summary: "Rounds up to a multiple of a significance",
+    },
+    Function {
+        name: "COUNTIF",
+        category: Category::Statistical,
+        min_args: 2,
+        max_args: Some(2),
+        args: &[Arg::Reference, Arg::Criteria],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("countif"),
+        summary: "Number of cells matching a criterion",
+    },
+    Function {
+        name: "COUNTIFS",
+        category: Category::Statistical,
+        min_args: 2,
+        max_args: Some(254),
+        args: &[Arg::Reference, Arg::Criteria],
+        repeat: 2,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("countifs"),
+        summary: "Number of cells matching every criterion",
+    },
+    Function {
+        name: "ERROR.TYPE",
+        category: Category::Information,
+        min_args: 1,
+        max_args: Some(1),
+        args: &[Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("error_type"),
+        summary: "Number identifying an error value",
+    },
+    Function {
+        name: "FIND",
+        category: Category::Text,
+        min_args: 2,
+        max_args: Some(3),
+        args: &[Arg::Text, Arg::Text, Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("find"),
+        summary: "Position of text within text, case-sensitively",
+    },
+    Function {
+        name: "FLOOR.MATH",
+        category: Category::Math,
+        min_args: 1,
+        max_args: Some(3),
+        args: &[Arg::Value, Arg::Value, Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("floor_math"),
+        summary: "Rounds down to a multiple of a significance",
+    },
+    Function {
+        name: "HLOOKUP",
+        category: Category::Lookup,
+        min_args: 3,
+        max_args: Some(4),
+        args: &[Arg::Value, Arg::Range, Arg::Value, Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Any,
+        python: Translation::Helper("hlookup"),
+        summary: "The cell in a given row below a value found in the first row of a table",
+    },
+    Function {
+        name: "IF",
+        category: Category::Logical,
+        min_args: 2,
+        max_args: Some(3),
+        args: &[Arg::Value, Arg::Value, Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Any,
+        python: Translation::Custom("then if condition else otherwise"),
+        summary: "One of two values depending on a condition",
+    },
+    Function {
+        name: "IFERROR",
+        category: Category::Logical,
+        min_args: 2,
+        max_args: Some(2),
+        args: &[Arg::Value, Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Any,
+        python: Translation::Helper("iferror"),
+        summary: "Fallback value when an expression is an error",
+    },
+    Function {
+        name: "IFNA",
+        category: Category::Logical,
+        min_args: 2,
+        max_args: Some(2),
+        args: &[Arg::Value, Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Any,
+        python: Translation::Helper("ifna"),
+        summary: "Fallback value when an expression is #N/A",
+    },
+    Function {
+        name: "IFS",
+        category: Category::Logical,
+        min_args: 2,
+        max_args: Some(254),
+        args: &[Arg::Value, Arg::Value],
+        repeat: 2,
+        volatile: false,
+        returns: ReturnType::Any,
+        python: Translation::Custom("then if condition else ... else ExcelError(\"#N/A\")"),
+        summary: "The value paired with the first TRUE condition",
+    },
+    Function {
+        name: "INDEX",
+        category: Category::Lookup,
+        min_args: 2,
+        max_args: Some(4),
+        args: &[Arg::Range, Arg::Value, Arg::Value, Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Reference,
+        python: Translation::Helper("index"),
+        summary: "The cell at a row and column of a range",
+    },
+    Function {
+        name: "INT",
+        category: Category::Math,
+        min_args: 1,
+        max_args: Some(1),
+        args: &[Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("int_"),
+        summary: "Rounds down to the nearest integer",
+    },
+    Function {
+        name: "ISERR",
+        category: Category::Information,
+        min_args: 1,
+        max_args: Some(1),
+        args: &[Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Bool,
+        python: Translation::Helper("iserr"),
+        summary: "Whether a value is an error other than #N/A",
+    },
+    Function {
+        name: "ISERROR",
+        category: Category::Information,
+        min_args: 1,
+        max_args: Some(1),
+        args: &[Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Bool,
+        python: Translation::Helper("iserror"),
+        summary: "Whether a value is any error",
+    },
+    Function {
+        name: "ISNA",
+        category: Category::Information,
+        min_args: 1,
+        max_args: Some(1),
+        args: &[Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Bool,
+        python: Translation::Helper("isna"),
+        summary: "Whether a value is #N/A",
+    },
+    Function {
+        name: "LEFT",
+        category: Category::Text,
+        min_args: 1,
+        max_args: Some(2),
+        args: &[Arg::Text, Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Text,
+        python: Translation::Helper("left"),
+        summary: "The first characters of text",
+    },
+    Function {
+        name: "MATCH",
+        category: Category::Lookup,
+        min_args: 2,
+        max_args: Some(3),
+        args: &[Arg::Value, Arg::Range, Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("match_"),
+        summary: "Position of a value in a row or column",
+    },
+    Function {
+        name: "MAXIFS",
+        category: Category::Statistical,
+        min_args: 3,
+        max_args: None,
+        args: &[Arg::Reference, Arg::Reference, Arg::Criteria],
+        repeat: 2,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("maxifs"),
+        summary: "Largest of the cells matching every criterion",
+    },
+    Function {
+        name: "MID",
+        category: Category::Text,
+        min_args: 3,
+        max_args: Some(3),
+        args: &[Arg::Text, Arg::Value, Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Text,
+        python: Translation::Helper("mid"),
+        summary: "Characters from the middle of text",
+    },
+    Function {
+        name: "MINIFS",
+        category: Category::Statistical,
+        min_args: 3,
+        max_args: None,
+        args: &[Arg::Reference, Arg::Reference, Arg::Criteria],
+        repeat: 2,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("minifs"),
+        summary: "Smallest of the cells matching every criterion",
+    },
+    Function {
+        name: "MOD",
+        category: Category::Math,
+        min_args: 2,
+        max_args: Some(2),
+        args: &[Arg::Value, Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("mod"),
+        summary: "Remainder of a division, with the divisor's sign",
+    },
+    Function {
+        name: "MROUND",
+        category: Category::Math,
+        min_args: 2,
+        max_args: Some(2),
+        args: &[Arg::Value, Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("mround"),
+        summary: "Rounds to the nearest multiple of a number",
+    },
+    Function {
+        name: "NA",
+        category: Category::Information,
+        min_args: 0,
+        max_args: Some(0),
+        args: &[],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Any,
+        python: Translation::Custom("ExcelError(\"#N/A\")"),
+        summary: "The #N/A error value",
+    },
+    Function {
+        name: "NOT",
+        category: Category::Logical,
+        min_args: 1,
+        max_args: Some(1),
+        args: &[Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Bool,
+        python: Translation::Helper("not_"),
+        summary: "Reverses a logical value",
+    },
+    Function {
+        name: "OR",
+        category: Category::Logical,
+        min_args: 1,
+        max_args: None,
+        args: &[Arg::Range],
+        repeat: 1,
+        volatile: false,
+        returns: ReturnType::Bool,
+        python: Translation::Helper("or_"),
+        summary: "Whether any logical value is TRUE, skipping text and blanks in ranges",
+    },
+    Function {
+        name: "POWER",
+        category: Category::Math,
+        min_args: 2,
+        max_args: Some(2),
+        args: &[Arg::Value, Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Custom("number ** power, or power(number, power)"),
+        summary: "A number raised to a power, like ^",
+    },
+    Function {
+        name: "RIGHT",
+        category: Category::Text,
+        min_args: 1,
+        max_args: Some(2),
+        args: &[Arg::Text, Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Text,
+        python: Translation::Helper("right"),
+        summary: "The last characters of text",
+    },
+    Function {
+        name: "ROUND",
+        category: Category::Math,
+        min_args: 2,
+        max_args: Some(2),
+        args: &[Arg::Value, Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("round_"),
+        summary: "Rounds half away from zero to a number of digits",
+    },
+    Function {
+        name: "ROUNDDOWN",
+        category: Category::Math,
+        min_args: 2,
+        max_args: Some(2),
+        args: &[Arg::Value, Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("rounddown"),
+        summary: "Rounds toward zero to a number of digits",
+    },
+    Function {
+        name: "ROUNDUP",
+        category: Category::Math,
+        min_args: 2,
+        max_args: Some(2),
+        args: &[Arg::Value, Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("roundup"),
+        summary: "Rounds away from zero to a number of digits",
+    },
+    Function {
+        name: "SEARCH",
+        category: Category::Text,
+        min_args: 2,
+        max_args: Some(3),
+        args: &[Arg::Text, Arg::Text, Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("search"),
+        summary: "Position of text within text, ignoring case and allowing wildcards",
+    },
+    Function {
+        name: "SUBSTITUTE",
+        category: Category::Text,
+        min_args: 3,
+        max_args: Some(4),
+        args: &[Arg::Text, Arg::Text, Arg::Text, Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Text,
+        python: Translation::Helper("substitute"),
+        summary: "Text with occurrences of other text replaced",
+    },
+    Function {
+        name: "SUM",
+        category: Category::Math,
+        min_args: 1,
+        max_args: None,
+        args: &[Arg::Range],
+        repeat: 1,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("sum_"),
+        summary: "Adds numbers, skipping text and blanks in ranges",
+    },
+    Function {
+        name: "SUMIF",
+        category: Category::Math,
+        min_args: 2,
+        max_args: Some(3),
+        args: &[Arg::Reference, Arg::Criteria, Arg::Reference],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("sumif"),
+        summary: "Adds the cells matching a criterion",
+    },
+    Function {
+        name: "SUMIFS",
+        category: Category::Math,
+        min_args: 3,
+        max_args: None,
+        args: &[Arg::Reference, Arg::Reference, Arg::Criteria],
+        repeat: 2,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("sumifs"),
+        summary: "Adds the cells matching every criterion",
+    },
+    Function {
+        name: "SUMPRODUCT",
+        category: Category::Math,
+        min_args: 1,
+        max_args: None,
+        args: &[Arg::Range],
+        repeat: 1,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("sumproduct"),
+        summary: "Sum of the products of matching cells in equally sized ranges",
+    },
+    Function {
+        name: "SWITCH",
+        category: Category::Logical,
+        min_args: 3,
+        max_args: Some(254),
+        args: &[Arg::Value, Arg::Value, Arg::Value],
+        repeat: 1,
+        volatile: false,
+        returns: ReturnType::Any,
+        python: Translation::Helper("switch"),
+        summary: "The result paired with the first value matching an expression",
+    },
+    Function {
+        name: "TEXT",
+        category: Category::Text,
+        min_args: 2,
+        max_args: Some(2),
+        args: &[Arg::Value, Arg::Text],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Text,
+        python: Translation::Custom("text_format(value, sections)"),
+        summary: "A number formatted with a number format code",
+    },
+    Function {
+        name: "TEXTJOIN",
+        category: Category::Text,
+        min_args: 3,
+        max_args: Some(254),
+        args: &[Arg::Text, Arg::Value, Arg::Range],
+        repeat: 1,
+        volatile: false,
+        returns: ReturnType::Text,
+        python: Translation::Helper("textjoin"),
+        summary: "Text and ranges joined with a delimiter",
+    },
+    Function {
+        name: "TEXTSPLIT",
+        category: Category::Text,
+        min_args: 2,
+        max_args: Some(6),
+        args: &[
+            Arg::Text,
+            Arg::Text,
+            Arg::Text,
+            Arg::Value,
+            Arg::Value,
+            Arg::Value,
+        ],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Array,
+        python: Translation::Helper("textsplit"),
+        summary: "Text split into rows and columns at delimiters",
+    },
+    Function {
+        name: "TRIM",
+        category: Category::Text,
+        min_args: 1,
+        max_args: Some(1),
+        args: &[Arg::Text],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Text,
+        python: Translation::Helper("trim"),
+        summary: "Text without extra spaces",
+    },
+    Function {
+        name: "VLOOKUP",
+        category: Category::Lookup,
+        min_args: 3,
+        max_args: Some(4),
+        args: &[Arg::Value, Arg::Range, Arg::Value, Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Any,
+        python: Translation::Helper("vlookup"),
+        summary: "The cell in a given column beside a value found in the first column of a table",
+    },
+    Function {
+        name: "XLOOKUP",
+        category: Category::Lookup,
+        min_args: 3,
+        max_args: Some(6),
+        args: &[
+            Arg::Value,
+            Arg::Range,
+            Arg::Range,
+            Arg::Value,
+            Arg::Value,
+            Arg::Value,
+        ],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Any,
+        python: Translation::Helper("xlookup"),
+        summary: "The cells of one range beside a value found in another",
+    },
+    Function {
+        name: "XMATCH",
+        category: Category::Lookup,
+        min_args: 2,
+        max_args: Some(4),
+        args: &[Arg::Value, Arg::Range, Arg::Value, Arg::Value],
+        repeat: 0,
+        volatile: false,
+        returns: ReturnType::Number,
+        python: Translation::Helper("xmatch"),
+        summary: "Position of a value in a row or column, with match and search modes",
+    },
+    Function {
+        name: "XOR",
+        category: Category::Logical,
+        min_args: 1,
+        max_args: None,
+        args: &[Arg::Range],
+        repeat: 1,
+        volatile: false,
+        returns: ReturnType::Bool,
+        python: Translation::Helper("xor"),
+        summary: "Whether an odd number of logical values are TRUE",
+    },
+];
+
+/// Looks up a supported function by its canonical uppercase name
+pub fn lookup(name: &str) -> Option<&'static Function> {
+    FUNCTIONS
+        .binary_search_by(|function| function.name.cmp(name))
+        .ok()
+        .map(|index| &FUNCTIONS[index])
+}
+
+/// A catalog entry as seen from JavaScript
+#[wasm_bindgen]
+pub struct FunctionInfo {
+    function: &'static Function,
+}
+
+impl From<&'static Function> for FunctionInfo {
+    fn from(function: &'static Function) -> Self {
+        Self { function }
+    }
+}
+
+#[wasm_bindgen]
+impl FunctionInfo {
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.function.name.to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn category(&self) -> String {
+        format!("{:?}", self.function.category)
+    }
+
+    #[wasm_bindgen(getter, js_name = minArgs)]
+    pub fn min_args(&self) -> usize {
+        self.function.min_args
+    }
+
+    /// `undefined` for functions taking any number of arguments
+    #[wasm_bindgen(getter, js_name = maxArgs)]
+    pub fn max_args(&self) -> Option<usize> {
+        self.function.max_args
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn volatile(&self) -> bool {
+        self.function.volatile
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn returns(&self) -> String {
+        format!("{:?}", self.function.returns)
+    }
+
+    /// One kind per parameter, such as `"Value"` or `"Criteria"`; the
+    /// trailing kinds that repeat end in `"..."`, e.g. `["Range..."]` for SUM
+    #[wasm_bindgen(getter, js_name = argKinds)]
+    pub fn arg_kinds(&self) -> Vec<String> {
+        let function = self.function;
+        let first_repeated = function.args.len() - function.repeat;
+        function
+            .args
+            .iter()
+            .enumerate()
+            .map(|(index, arg)| {
+                if index >= first_repeated {
+                    format!("{arg:?}...")
+                } else {
+                    format!("{arg:?}")
+                }
+            })
+            .collect()
+    }
+
+    /// The runtime helper the function calls, or a sketch of the
+    /// expression written for it, like `"ExcelError(\"#N/A\")"` for NA
+    #[wasm_bindgen(getter)]
+    pub fn python(&self) -> String {
+        match self.function.python {
+            Translation::Helper(name) | Translation::Custom(name) => name.to_string(),
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn summary(&self) -> String {
+        self.function.summary.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bindings::builtins;
+    use crate::codegen::runtime;
+
+    #[test]
+    fn test_catalog_is_sorted() {
+        assert!(FUNCTIONS.windows(2).all(|w| w[0].name < w[1].name));
+    }
+
+    #[test]
+    fn test_names_are_builtins() {
+        for function in FUNCTIONS {
+            assert!(
+                builtins::BUILTIN_FUNCTIONS.contains(&function.name),
+                "{}",
+                function.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_arities_are_consistent() {
+        for function in FUNCTIONS {
+            let max = function.max_args.unwrap_or(MAX_ARGS);
+            assert!(function.min_args <= max, "{}", function.name);
+            if function.repeat == 0 {
+                assert_eq!(function.args.len(), max, "{}", function.name);
+            } else {
+                assert!(function.args.len() >= function.repeat, "{}", function.name);
+                assert!(function.accepts(function.min_args), "{}", function.name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_helpers_exist() {
+        for function in FUNCTIONS {
+            if let Translation::Helper(name) = function.python {
+                assert!(runtime::helper(name).is_some(), "{name}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_readme_lists_every_function() {
+        let readme = include_str!("../README.md");
+        for function in FUNCTIONS {
+            assert!(
+                readme.contains(&format!("`{}`", function.name)),
+                "{}",
+                function.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_lookup() {
+        assert_eq!(lookup("SUM").map(|f| f.name), Some("SUM"));
+        assert!(lookup("sum").is_none());
+        assert!(lookup("VLOOKUP2").is_none());
+    }
+
+    #[test]
+    fn test_variadic_args_repeat() {
+        let sum = lookup("SUM").unwrap();
+        assert_eq!(sum.arg(0), Some(Arg::Range));
+        assert_eq!(sum.arg(254), Some(Arg::Range));
+        assert_eq!(sum.arg(255), None);
+        assert!(sum.accepts(255));
+        assert!(!sum.accepts(0));
+    }
+
+    #[test]
+    fn test_paired_args_repeat() {
+        let ifs = lookup("IFS").unwrap();
+        assert!(ifs.accepts(2));
+        assert!(!ifs.accepts(3));
+        assert!(ifs.accepts(254));
+        assert!(!ifs.accepts(256));
+        assert_eq!(ifs.arg(5), Some(Arg::Value));
+    }
+
+    #[test]
+    fn test_criteria_pairs_follow_the_first_range() {
+        let sumifs = lookup("SUMIFS").unwrap();
+        assert!(sumifs.accepts(3));
+        assert!(!sumifs.accepts(4));
+        assert!(sumifs.accepts(255));
+        assert_eq!(sumifs.arg(3), Some(Arg::Reference));
+        assert_eq!(sumifs.arg(4), Some(Arg::Criteria));
+    }
+
+    #[test]
+    fn test_info_arg_kinds() {
+        let info = |name| FunctionInfo::from(lookup(name).unwrap());
+        assert_eq!(info("SUM").arg_kinds(), ["Range..."]);
+        assert_eq!(info("IFS").arg_kinds(), ["Value...", "Value..."]);
+        assert_eq!(info("TEXTJOIN").arg_kinds(), ["Text", "Value", "Range..."]);
+        assert_eq!(
+            info("SUMIFS").arg_kinds(),
+            ["Reference", "Reference...", "Criteria..."]
+        );
+        assert_eq!(info("MOD").arg_kinds(), ["Value", "Value"]);
+    }
+
+    #[test]
+    fn test_info_python() {
+        let info = |name| FunctionInfo::from(lookup(name).unwrap());
+        assert_eq!(info("SUM").python(), "sum_");
+        assert_eq!(info("NA").python(), "ExcelError(\"#N/A\")");
+        assert_eq!(info("IF").python(), "then if condition else otherwise");
+    }
+
+    #[test]
+    fn test_describe_arity() {
+        assert_eq!(describe_arity(1, Some(1), 0), "1 argument");
+        assert_eq!(describe_arity(2, Some(3), 0), "2-3 arguments");
+        assert_eq!(describe_arity(1, None, 1), "at least 1 argument");
+        assert_eq!(describe_arity(0, Some(0), 0), "0 arguments");
+        assert_eq!(describe_arity(2, Some(254), 2), "2, 4, 6, ... arguments");
+    }
+}
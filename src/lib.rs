@@ -1,40 +1,190 @@
+use std::fmt;
+
 use wasm_bindgen::prelude::*;
 
 pub mod bindings;
+pub mod codegen;
+pub mod criteria;
+pub mod format;
+pub mod functions;
+
+use bindings::ast::ParseError;
+use bindings::lexer::Lexer;
+use bindings::parser::Parser;
+use bindings::token::{LexerError, Span};
+use codegen::CodegenError;
+use codegen::python::PythonEmitter;
+use codegen::runtime;
+use functions::{FUNCTIONS, FunctionInfo};
+
+pub use codegen::{Backend, ReferenceStyle, TranspileOptions};
+
+/// Any failure while turning a formula into Python
+#[derive(Debug, Clone, PartialEq)]
+pub enum TranspileError {
+    Lex(LexerError),
+    Parse(ParseError),
+    Codegen(CodegenError),
+}
+
+impl TranspileError {
+    /// Location of the offending input, when known
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Self::Lex(err) => Some(err.span()),
+            Self::Parse(err) => Some(err.span()),
+            Self::Codegen(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for TranspileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(span) = self.span() {
+            write!(f, "line {}, column {}: ", span.line, span.column)?;
+        }
+        match self {
+            Self::Lex(err) => write!(f, "{err}"),
+            Self::Parse(err) => write!(f, "{err}"),
+            Self::Codegen(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for TranspileError {}
 
-#[wasm_bindgen(start)]
-fn init() -> Result<(), JsValue> {
-    // Use `web_sys`'s global `window` function to get a handle on the global
-    // window object.
-    let window = web_sys::window().expect("no global `window` exists");
-    let document = window.document().expect("should have a document on window");
-    let body = document.body().expect("document should have a body");
+impl From<LexerError> for TranspileError {
+    fn from(err: LexerError) -> Self {
+        Self::Lex(err)
+    }
+}
+
+impl From<ParseError> for TranspileError {
+    fn from(err: ParseError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+impl From<CodegenError> for TranspileError {
+    fn from(err: CodegenError) -> Self {
+        Self::Codegen(err)
+    }
+}
+
+/// Transpiles an Excel formula into an equivalent Python expression.
+///
+/// Helpers the expression needs are imported from the `expy_runtime`
+/// module, whose source [`runtime_module`] returns.
+pub fn transpile(formula: &str) -> Result<String, TranspileError> {
+    transpile_with(formula, &TranspileOptions::default())
+}
 
-    // Manufacture the element we're gonna append
-    let val = document.create_element("p")?;
-    val.set_inner_html("Hello from Rust!");
+/// [`transpile`] with explicit options
+pub fn transpile_with(formula: &str, options: &TranspileOptions) -> Result<String, TranspileError> {
+    let tokens = Lexer::new(formula).tokenize()?;
+    let expr = Parser::new(tokens).parse()?;
+    let python = PythonEmitter::with_options(options.clone()).emit_program(&expr)?;
+    Ok(python)
+}
 
-    body.append_child(&val)?;
+/// Source of `expy_runtime.py`, to be saved next to transpiled code
+pub fn runtime_module() -> String {
+    runtime::module()
+}
 
-    Ok(())
+/// Wasm entry point for [`transpile`]; errors surface as JS `Error`s
+#[wasm_bindgen(js_name = transpile)]
+pub fn transpile_js(formula: &str) -> Result<String, JsError> {
+    transpile(formula).map_err(|err| JsError::new(&err.to_string()))
 }
 
-#[wasm_bindgen]
-pub fn add(a: u32, b: u32) -> u32 {
-    a + b
+/// Wasm entry point for [`transpile_with`]
+#[wasm_bindgen(js_name = transpileWith)]
+pub fn transpile_with_js(formula: &str, options: &TranspileOptions) -> Result<String, JsError> {
+    transpile_with(formula, options).map_err(|err| JsError::new(&err.to_string()))
 }
 
+/// Wasm entry point for [`runtime_module`]
+#[wasm_bindgen(js_name = runtimeModule)]
+pub fn runtime_module_js() -> String {
+    runtime_module()
+}
+
+/// Wasm export of the function catalog, for listing supported functions
+#[wasm_bindgen(js_name = supportedFunctions)]
+pub fn supported_functions_js() -> Vec<FunctionInfo> {
+    FUNCTIONS.iter().map(FunctionInfo::from).collect()
+}
+
+/// Compiles the README's Rust examples as doctests
+#[cfg(doctest)]
+#[doc = include_str!("../README.md")]
+pub struct ReadmeDoctests;
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_add() {
-        assert_eq!(add(1, 2), 3);
+    fn test_transpile_literal() {
+        assert_eq!(transpile("=42").unwrap(), "42");
+        assert_eq!(transpile("=\"hi\"").unwrap(), "\"hi\"");
+    }
+
+    #[test]
+    fn test_transpile_reference() {
+        assert_eq!(transpile("=Sheet2!B3").unwrap(), "xl(\"Sheet2!B3\")");
+    }
+
+    #[test]
+    fn test_transpile_imports_helpers() {
+        assert_eq!(
+            transpile("=A1&\"x\"").unwrap(),
+            "from expy_runtime import concat\n\nconcat(xl(\"A1\"), \"x\")"
+        );
+    }
+
+    #[test]
+    fn test_transpile_inlines_used_helpers() {
+        let options = TranspileOptions {
+            inline_helpers: true,
+            ..Default::default()
+        };
+        let python = transpile_with("=IFERROR(#N/A,1)", &options).unwrap();
+        assert!(python.starts_with("class ExcelError:"));
+        assert!(python.contains("def iferror("));
+        assert!(!python.contains("def concat("));
+        assert!(!python.contains("import"));
+    }
+
+    #[test]
+    fn test_runtime_module_versioned() {
+        let module = runtime_module();
+        assert!(module.contains(env!("CARGO_PKG_VERSION")));
+    }
+
+    #[test]
+    fn test_transpile_lexer_error() {
+        let err = transpile("=1+?").unwrap_err();
+        assert!(matches!(err, TranspileError::Lex(_)));
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 4: unexpected character '?'"
+        );
+    }
+
+    #[test]
+    fn test_transpile_parse_error() {
+        let err = transpile("=(1").unwrap_err();
+        assert!(matches!(err, TranspileError::Parse(_)));
     }
 
     #[test]
-    fn test_add_zero() {
-        assert_eq!(add(0, 0), 0);
+    fn test_transpile_argument_count_error() {
+        let err = transpile("=IFERROR(A1)").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 2: IFERROR expects 2 arguments"
+        );
     }
 }
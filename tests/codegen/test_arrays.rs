@@ -0,0 +1,50 @@
+use expy::bindings::lexer::Lexer;
+use expy::bindings::parser::Parser;
+use expy::codegen::python::PythonEmitter;
+
+fn python(formula: &str) -> String {
+    let tokens = Lexer::new(formula).tokenize().unwrap();
+    let expr = Parser::new(tokens).parse().unwrap();
+    PythonEmitter::new().emit(&expr).unwrap()
+}
+
+// ============================================================================
+// SPEC: array constants become nested lists, one list per row
+// ============================================================================
+
+#[test]
+fn test_row_vector() {
+    assert_eq!(python("={1,2,3}"), "[[1, 2, 3]]");
+}
+
+#[test]
+fn test_column_vector() {
+    assert_eq!(python("={1;2;3}"), "[[1], [2], [3]]");
+}
+
+#[test]
+fn test_matrix() {
+    assert_eq!(python("={1,2;3,4}"), "[[1, 2], [3, 4]]");
+}
+
+#[test]
+fn test_negative_numbers() {
+    assert_eq!(python("={-1,2.5;-0.25,+4}"), "[[-1, 2.5], [-0.25, 4]]");
+}
+
+#[test]
+fn test_mixed_constants() {
+    assert_eq!(
+        python("={\"a\",TRUE;FALSE,#N/A}"),
+        "[[\"a\", True], [False, ExcelError(\"#N/A\")]]"
+    );
+}
+
+#[test]
+fn test_ragged_rows_fail_transpile() {
+    let err = expy::transpile("={1,2;3}").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "line 1, column 7: array row 2 has 1 value, expected 2 like the first row"
+    );
+}
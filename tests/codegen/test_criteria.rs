@@ -0,0 +1,160 @@
+use expy::bindings::lexer::Lexer;
+use expy::bindings::parser::Parser;
+use expy::codegen::python::PythonEmitter;
+
+fn emit(formula: &str) -> (String, Vec<&'static str>) {
+    let tokens = Lexer::new(formula).tokenize().unwrap();
+    let expr = Parser::new(tokens).parse().unwrap();
+    let mut emitter = PythonEmitter::new();
+    let python = emitter.emit(&expr).unwrap();
+    (python, emitter.helpers().collect())
+}
+
+fn python(formula: &str) -> String {
+    emit(formula).0
+}
+
+// ============================================================================
+// SPEC: criteria literals are parsed when transpiling
+// ============================================================================
+
+#[test]
+fn test_comparison_criteria() {
+    let (countif, helpers) = emit("=COUNTIF(A1:A10,\">=10\")");
+    assert_eq!(countif, "countif(xl(\"A1:A10\"), criterion(\">=\", 10))");
+    assert_eq!(helpers, ["countif", "criterion"]);
+    assert_eq!(
+        python("=COUNTIF(A1:A10,\"<2.5\")"),
+        "countif(xl(\"A1:A10\"), criterion(\"<\", 2.5))"
+    );
+}
+
+#[test]
+fn test_text_criteria() {
+    assert_eq!(
+        python("=COUNTIF(A1:A10,\"apple\")"),
+        "countif(xl(\"A1:A10\"), criterion(\"=\", \"apple\"))"
+    );
+    assert_eq!(
+        python("=COUNTIF(A1:A10,\"a*b?\")"),
+        "countif(xl(\"A1:A10\"), criterion(\"=\", \"a*b?\"))"
+    );
+    assert_eq!(
+        python("=COUNTIF(A1:A10,\"<>~*\")"),
+        "countif(xl(\"A1:A10\"), criterion(\"<>\", \"~*\"))"
+    );
+}
+
+#[test]
+fn test_text_criteria_with_quotes() {
+    assert_eq!(
+        python("=COUNTIF(A1:A10,\"say \"\"hi\"\"\")"),
+        "countif(xl(\"A1:A10\"), criterion(\"=\", \"say \\\"hi\\\"\"))"
+    );
+}
+
+#[test]
+fn test_blank_criteria() {
+    assert_eq!(
+        python("=COUNTIF(A1:A10,\"<>\")"),
+        "countif(xl(\"A1:A10\"), criterion(\"<>\", None))"
+    );
+    assert_eq!(
+        python("=COUNTIF(A1:A10,\"\")"),
+        "countif(xl(\"A1:A10\"), criterion(\"=\", \"\"))"
+    );
+}
+
+#[test]
+fn test_boolean_and_error_criteria() {
+    assert_eq!(
+        python("=COUNTIF(A1:A10,\"true\")"),
+        "countif(xl(\"A1:A10\"), criterion(\"=\", True))"
+    );
+    let (countif, helpers) = emit("=COUNTIF(A1:A10,\"#n/a\")");
+    assert_eq!(
+        countif,
+        "countif(xl(\"A1:A10\"), criterion(\"=\", ExcelError(\"#N/A\")))"
+    );
+    assert_eq!(helpers, ["ExcelError", "countif", "criterion"]);
+}
+
+#[test]
+fn test_constant_criteria() {
+    assert_eq!(
+        python("=COUNTIF(A1:A10,10)"),
+        "countif(xl(\"A1:A10\"), criterion(\"=\", 10))"
+    );
+    assert_eq!(
+        python("=COUNTIF(A1:A10,FALSE)"),
+        "countif(xl(\"A1:A10\"), criterion(\"=\", False))"
+    );
+}
+
+#[test]
+fn test_computed_criteria_are_parsed_at_run_time() {
+    assert_eq!(
+        python("=COUNTIF(A1:A10,B1)"),
+        "countif(xl(\"A1:A10\"), xl(\"B1\"))"
+    );
+    assert_eq!(
+        python("=COUNTIF(A1:A10,\">\"&B1)"),
+        "countif(xl(\"A1:A10\"), concat(\">\", xl(\"B1\")))"
+    );
+}
+
+// ============================================================================
+// SPEC: conditional aggregates
+// ============================================================================
+
+#[test]
+fn test_sumif() {
+    assert_eq!(
+        python("=SUMIF(A1:A10,\">0\")"),
+        "sumif(xl(\"A1:A10\"), criterion(\">\", 0))"
+    );
+    assert_eq!(
+        python("=SUMIF(A1:A10,\"x\",B1:B10)"),
+        "sumif(xl(\"A1:A10\"), criterion(\"=\", \"x\"), xl(\"B1:B10\"))"
+    );
+}
+
+#[test]
+fn test_ifs_criteria_pairs() {
+    assert_eq!(
+        python("=SUMIFS(C1:C10,A1:A10,\">0\",B1:B10,\"x*\")"),
+        "sumifs(xl(\"C1:C10\"), xl(\"A1:A10\"), criterion(\">\", 0), xl(\"B1:B10\"), criterion(\"=\", \"x*\"))"
+    );
+    assert_eq!(
+        python("=COUNTIFS(A1:A10,\"<>\",B1:B10,5)"),
+        "countifs(xl(\"A1:A10\"), criterion(\"<>\", None), xl(\"B1:B10\"), criterion(\"=\", 5))"
+    );
+}
+
+#[test]
+fn test_average_max_min_ifs() {
+    assert_eq!(
+        python("=AVERAGEIF(A1:A10,\">0\",B1:B10)"),
+        "averageif(xl(\"A1:A10\"), criterion(\">\", 0), xl(\"B1:B10\"))"
+    );
+    assert_eq!(
+        python("=AVERAGEIFS(B1:B10,A1:A10,\">0\")"),
+        "averageifs(xl(\"B1:B10\"), xl(\"A1:A10\"), criterion(\">\", 0))"
+    );
+    assert_eq!(
+        python("=MAXIFS(B1:B10,A1:A10,\"a\")"),
+        "maxifs(xl(\"B1:B10\"), xl(\"A1:A10\"), criterion(\"=\", \"a\"))"
+    );
+    assert_eq!(
+        python("=MINIFS(B1:B10,A1:A10,\"a\")"),
+        "minifs(xl(\"B1:B10\"), xl(\"A1:A10\"), criterion(\"=\", \"a\"))"
+    );
+}
+
+#[test]
+fn test_aggregate_result_is_a_number() {
+    assert_eq!(
+        python("=COUNTIF(A1:A10,\"x\")>0"),
+        "countif(xl(\"A1:A10\"), criterion(\"=\", \"x\")) > 0"
+    );
+}
@@ -0,0 +1,85 @@
+use expy::bindings::lexer::Lexer;
+use expy::bindings::parser::Parser;
+use expy::codegen::CodegenError;
+use expy::codegen::python::PythonEmitter;
+
+fn emit(formula: &str) -> Result<(String, Vec<&'static str>), CodegenError> {
+    let tokens = Lexer::new(formula).tokenize().unwrap();
+    let expr = Parser::new(tokens).parse().unwrap();
+    let mut emitter = PythonEmitter::new();
+    let python = emitter.emit(&expr)?;
+    Ok((python, emitter.helpers().collect()))
+}
+
+fn python(formula: &str) -> String {
+    emit(formula).unwrap().0
+}
+
+// ============================================================================
+// SPEC: error literals
+// ============================================================================
+
+#[test]
+fn test_error_literal() {
+    let (python, helpers) = emit("=#N/A").unwrap();
+    assert_eq!(python, "ExcelError(\"#N/A\")");
+    assert_eq!(helpers, ["ExcelError"]);
+}
+
+#[test]
+fn test_error_ref_literal() {
+    assert_eq!(python("=#REF!"), "ExcelError(\"#REF!\")");
+    assert_eq!(python("=Sheet1!#REF!"), "ExcelError(\"#REF!\")");
+}
+
+#[test]
+fn test_error_in_arithmetic() {
+    assert_eq!(
+        python("=1+#DIV/0!"),
+        "1 + to_number(ExcelError(\"#DIV/0!\"))"
+    );
+}
+
+#[test]
+fn test_na_function() {
+    assert_eq!(python("=NA()"), "ExcelError(\"#N/A\")");
+}
+
+// ============================================================================
+// SPEC: error inspection functions
+// ============================================================================
+
+#[test]
+fn test_iferror() {
+    let (python, helpers) = emit("=IFERROR(A1/B1,0)").unwrap();
+    assert_eq!(python, "iferror(divide(xl(\"A1\"), xl(\"B1\")), 0)");
+    assert_eq!(helpers, ["divide", "iferror"]);
+}
+
+#[test]
+fn test_is_functions() {
+    assert_eq!(python("=ISNA(A1)"), "isna(xl(\"A1\"))");
+    assert_eq!(python("=ISERROR(A1)"), "iserror(xl(\"A1\"))");
+    assert_eq!(python("=ISERR(A1)"), "iserr(xl(\"A1\"))");
+}
+
+#[test]
+fn test_error_type() {
+    assert_eq!(
+        python("=ERROR.TYPE(#NULL!)"),
+        "error_type(ExcelError(\"#NULL!\"))"
+    );
+}
+
+#[test]
+fn test_is_function_result_compares_natively() {
+    assert_eq!(python("=ISNA(A1)=TRUE"), "isna(xl(\"A1\")) == True");
+}
+
+#[test]
+fn test_unsupported_function() {
+    assert!(matches!(
+        emit("=FOO.BAR(1)"),
+        Err(CodegenError::Unsupported(_))
+    ));
+}
@@ -0,0 +1,151 @@
+use expy::bindings::lexer::Lexer;
+use expy::bindings::parser::Parser;
+use expy::codegen::python::PythonEmitter;
+
+fn emit(formula: &str) -> (String, Vec<&'static str>) {
+    let tokens = Lexer::new(formula).tokenize().unwrap();
+    let expr = Parser::new(tokens).parse().unwrap();
+    let mut emitter = PythonEmitter::new();
+    let python = emitter.emit(&expr).unwrap();
+    (python, emitter.helpers().collect())
+}
+
+fn python(formula: &str) -> String {
+    emit(formula).0
+}
+
+// ============================================================================
+// SPEC: IF as a conditional expression
+// ============================================================================
+
+#[test]
+fn test_if() {
+    let (python, helpers) = emit("=IF(A1,1,2)");
+    assert_eq!(
+        python,
+        "1 if (cond := to_bool(xl(\"A1\"))) is True else cond if iserror(cond) else 2"
+    );
+    assert_eq!(helpers, ["iserror", "to_bool"]);
+}
+
+#[test]
+fn test_if_without_else_is_false() {
+    let (python, helpers) = emit("=IF(A1>0,\"yes\")");
+    assert_eq!(
+        python,
+        "\"yes\" if (cond := to_bool(compare(xl(\"A1\"), 0) > 0)) is True else cond"
+    );
+    assert_eq!(helpers, ["compare", "to_bool"]);
+}
+
+#[test]
+fn test_if_empty_branch_is_zero() {
+    assert_eq!(
+        python("=IF(A1,,1)"),
+        "0 if (cond := to_bool(xl(\"A1\"))) is True else cond if iserror(cond) else 1"
+    );
+}
+
+#[test]
+fn test_nested_if_in_else_chains() {
+    assert_eq!(
+        python("=IF(A1,1,IF(B1,2,3))"),
+        "1 if (cond := to_bool(xl(\"A1\"))) is True else cond if iserror(cond) else \
+         2 if (cond := to_bool(xl(\"B1\"))) is True else cond if iserror(cond) else 3"
+    );
+}
+
+#[test]
+fn test_nested_if_in_then_is_parenthesized() {
+    assert_eq!(
+        python("=IF(A1,IF(B1,1,2),3)"),
+        "(1 if (cond := to_bool(xl(\"B1\"))) is True else cond if iserror(cond) else 2) \
+         if (cond := to_bool(xl(\"A1\"))) is True else cond if iserror(cond) else 3"
+    );
+}
+
+#[test]
+fn test_if_in_operator_is_parenthesized() {
+    assert_eq!(
+        python("=IF(A1,1,2)*3"),
+        "(1 if (cond := to_bool(xl(\"A1\"))) is True else cond if iserror(cond) else 2) * 3"
+    );
+}
+
+#[test]
+fn test_if_keeps_branch_kind() {
+    // Both branches are text, so & stays native
+    assert_eq!(
+        python("=IF(A1,\"a\",\"b\")&\"!\""),
+        "(\"a\" if (cond := to_bool(xl(\"A1\"))) is True else cond if iserror(cond) else \"b\") + \"!\""
+    );
+}
+
+// ============================================================================
+// SPEC: IFS
+// ============================================================================
+
+#[test]
+fn test_ifs() {
+    assert_eq!(
+        python("=IFS(A1,1,B1,2)"),
+        "1 if (cond := to_bool(xl(\"A1\"))) is True else cond if iserror(cond) else \
+         2 if (cond := to_bool(xl(\"B1\"))) is True else cond if iserror(cond) else \
+         ExcelError(\"#N/A\")"
+    );
+}
+
+// ============================================================================
+// SPEC: AND, OR, XOR, NOT
+// ============================================================================
+
+#[test]
+fn test_and_or_xor() {
+    let (and, helpers) = emit("=AND(A1:A3,B1)");
+    assert_eq!(and, "and_(xl(\"A1:A3\"), xl(\"B1\"))");
+    assert_eq!(helpers, ["and_"]);
+    assert_eq!(python("=OR(A1,TRUE)"), "or_(xl(\"A1\"), True)");
+    assert_eq!(python("=XOR(A1,B1)"), "xor(xl(\"A1\"), xl(\"B1\"))");
+}
+
+#[test]
+fn test_not() {
+    assert_eq!(python("=NOT(A1)"), "not_(xl(\"A1\"))");
+}
+
+#[test]
+fn test_logical_results_compare_natively() {
+    assert_eq!(
+        python("=AND(A1)=NOT(B1)"),
+        "and_(xl(\"A1\")) == not_(xl(\"B1\"))"
+    );
+}
+
+// ============================================================================
+// SPEC: SWITCH, IFERROR, IFNA
+// ============================================================================
+
+#[test]
+fn test_switch() {
+    assert_eq!(
+        python("=SWITCH(A1,1,\"one\",2,\"two\")"),
+        "switch(xl(\"A1\"), 1, \"one\", 2, \"two\")"
+    );
+}
+
+#[test]
+fn test_switch_with_default() {
+    assert_eq!(
+        python("=SWITCH(A1,1,\"one\",\"other\")"),
+        "switch(xl(\"A1\"), 1, \"one\", \"other\")"
+    );
+}
+
+#[test]
+fn test_iferror_and_ifna() {
+    assert_eq!(
+        python("=IFERROR(A1/B1,0)"),
+        "iferror(divide(xl(\"A1\"), xl(\"B1\")), 0)"
+    );
+    assert_eq!(python("=IFNA(A1,\"\")"), "ifna(xl(\"A1\"), \"\")");
+}
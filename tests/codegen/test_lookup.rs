@@ -0,0 +1,105 @@
+use expy::bindings::lexer::Lexer;
+use expy::bindings::parser::Parser;
+use expy::codegen::python::PythonEmitter;
+
+fn emit(formula: &str) -> (String, Vec<&'static str>) {
+    let tokens = Lexer::new(formula).tokenize().unwrap();
+    let expr = Parser::new(tokens).parse().unwrap();
+    let mut emitter = PythonEmitter::new();
+    let python = emitter.emit(&expr).unwrap();
+    (python, emitter.helpers().collect())
+}
+
+fn python(formula: &str) -> String {
+    emit(formula).0
+}
+
+// ============================================================================
+// SPEC: lookups call runtime helpers
+// ============================================================================
+
+#[test]
+fn test_vlookup() {
+    let (vlookup, helpers) = emit("=VLOOKUP(A1,Sheet2!A:C,3,FALSE)");
+    assert_eq!(vlookup, "vlookup(xl(\"A1\"), xl(\"Sheet2!A:C\"), 3, False)");
+    assert_eq!(helpers, ["vlookup"]);
+    assert_eq!(
+        python("=VLOOKUP(A1,B1:C5,2)"),
+        "vlookup(xl(\"A1\"), xl(\"B1:C5\"), 2)"
+    );
+}
+
+#[test]
+fn test_hlookup() {
+    assert_eq!(
+        python("=HLOOKUP(\"b\",A1:C3,2,FALSE)"),
+        "hlookup(\"b\", xl(\"A1:C3\"), 2, False)"
+    );
+}
+
+#[test]
+fn test_xlookup() {
+    assert_eq!(
+        python("=XLOOKUP(A1,B:B,C:D,\"none\")"),
+        "xlookup(xl(\"A1\"), xl(\"B:B\"), xl(\"C:D\"), \"none\")"
+    );
+    // An empty if_not_found still lets the modes through
+    assert_eq!(
+        python("=XLOOKUP(A1,B:B,C:C,,1,-1)"),
+        "xlookup(xl(\"A1\"), xl(\"B:B\"), xl(\"C:C\"), 0, 1, -1)"
+    );
+}
+
+#[test]
+fn test_match() {
+    let (found, helpers) = emit("=MATCH(\"b*\",A1:A5,0)");
+    assert_eq!(found, "match_(\"b*\", xl(\"A1:A5\"), 0)");
+    assert_eq!(helpers, ["match_"]);
+    assert_eq!(
+        python("=XMATCH(6,A1:A5,-1,2)"),
+        "xmatch(6, xl(\"A1:A5\"), -1, 2)"
+    );
+}
+
+// ============================================================================
+// SPEC: INDEX with literal positions is a reference
+// ============================================================================
+
+#[test]
+fn test_index_resolves_to_cell() {
+    let (index, helpers) = emit("=INDEX(B2:D4,2,3)");
+    assert_eq!(index, "xl(\"D3\")");
+    assert!(helpers.is_empty());
+    assert_eq!(python("=INDEX(A:A,3)"), "xl(\"A3\")");
+    assert_eq!(python("=INDEX(Sheet2!A:C,3,2)"), "xl(\"Sheet2!B3\")");
+}
+
+#[test]
+fn test_index_single_row_takes_column() {
+    assert_eq!(python("=INDEX(1:1,4)"), "xl(\"D1\")");
+    assert_eq!(python("=INDEX(B2:E2,2)"), "xl(\"C2\")");
+}
+
+#[test]
+fn test_index_at_runtime() {
+    // Computed positions, whole rows or columns, and positions outside the
+    // array are left to the helper
+    let (index, helpers) = emit("=INDEX(A1:C3,B1,1)");
+    assert_eq!(index, "index(xl(\"A1:C3\"), xl(\"B1\"), 1)");
+    assert_eq!(helpers, ["index"]);
+    assert_eq!(python("=INDEX(A1:C3,2)"), "index(xl(\"A1:C3\"), 2)");
+    assert_eq!(python("=INDEX(A1:C3,0,2)"), "index(xl(\"A1:C3\"), 0, 2)");
+    assert_eq!(python("=INDEX(A1:A3,5)"), "index(xl(\"A1:A3\"), 5)");
+    assert_eq!(
+        python("=INDEX({1,2;3,4},2,1)"),
+        "index([[1, 2], [3, 4]], 2, 1)"
+    );
+}
+
+#[test]
+fn test_index_match() {
+    assert_eq!(
+        python("=INDEX(B1:B5,MATCH(A1,A1:A5,0))"),
+        "index(xl(\"B1:B5\"), match_(xl(\"A1\"), xl(\"A1:A5\"), 0))"
+    );
+}
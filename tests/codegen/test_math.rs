@@ -0,0 +1,126 @@
+use expy::bindings::lexer::Lexer;
+use expy::bindings::parser::Parser;
+use expy::codegen::TranspileOptions;
+use expy::codegen::python::PythonEmitter;
+
+fn emit(formula: &str) -> (String, Vec<&'static str>) {
+    let tokens = Lexer::new(formula).tokenize().unwrap();
+    let expr = Parser::new(tokens).parse().unwrap();
+    let mut emitter = PythonEmitter::new();
+    let python = emitter.emit(&expr).unwrap();
+    (python, emitter.helpers().collect())
+}
+
+fn python(formula: &str) -> String {
+    emit(formula).0
+}
+
+// ============================================================================
+// SPEC: rounding
+// ============================================================================
+
+#[test]
+fn test_round_family() {
+    let (round, helpers) = emit("=ROUND(A1,2)");
+    assert_eq!(round, "round_(xl(\"A1\"), 2)");
+    assert_eq!(helpers, ["round_"]);
+    assert_eq!(python("=ROUNDUP(A1,-1)"), "roundup(xl(\"A1\"), -1)");
+    assert_eq!(python("=ROUNDDOWN(A1,0)"), "rounddown(xl(\"A1\"), 0)");
+    assert_eq!(python("=MROUND(A1,0.5)"), "mround(xl(\"A1\"), 0.5)");
+}
+
+#[test]
+fn test_round_empty_digits_is_zero() {
+    assert_eq!(python("=ROUND(A1,)"), "round_(xl(\"A1\"), 0)");
+}
+
+#[test]
+fn test_ceiling_and_floor_math() {
+    assert_eq!(python("=CEILING.MATH(A1)"), "ceiling_math(xl(\"A1\"))");
+    assert_eq!(
+        python("=FLOOR.MATH(A1,5,1)"),
+        "floor_math(xl(\"A1\"), 5, 1)"
+    );
+}
+
+#[test]
+fn test_rounding_result_is_a_number() {
+    assert_eq!(
+        python("=ROUND(A1,0)>INT(B1)"),
+        "round_(xl(\"A1\"), 0) > int_(xl(\"B1\"))"
+    );
+}
+
+// ============================================================================
+// SPEC: MOD, INT, ABS, POWER
+// ============================================================================
+
+#[test]
+fn test_mod_int_abs() {
+    assert_eq!(python("=MOD(A1,3)"), "mod(xl(\"A1\"), 3)");
+    assert_eq!(python("=INT(A1)"), "int_(xl(\"A1\"))");
+    assert_eq!(
+        python("=ABS(A1-B1)"),
+        "abs_(to_number(xl(\"A1\")) - to_number(xl(\"B1\")))"
+    );
+}
+
+#[test]
+fn test_power_matches_operator() {
+    assert_eq!(python("=POWER(A1,2)"), "to_number(xl(\"A1\")) ** 2");
+    assert_eq!(python("=POWER(A1,B1)"), python("=A1^B1"));
+    assert_eq!(python("=POWER(2,-1)"), "power(2, -1)");
+}
+
+#[test]
+fn test_power_in_operator_is_parenthesized() {
+    assert_eq!(python("=-POWER(A1,2)"), "-to_number(xl(\"A1\")) ** 2");
+    assert_eq!(python("=POWER(A1+1,2)"), "(to_number(xl(\"A1\")) + 1) ** 2");
+}
+
+// ============================================================================
+// SPEC: SUMPRODUCT
+// ============================================================================
+
+#[test]
+fn test_sumproduct() {
+    assert_eq!(
+        python("=SUMPRODUCT(A1:A3,B1:B3)"),
+        "sumproduct(xl(\"A1:A3\"), xl(\"B1:B3\"))"
+    );
+}
+
+#[test]
+fn test_sumproduct_array_constants() {
+    assert_eq!(
+        python("=SUMPRODUCT({1,2},{3,4})"),
+        "sumproduct([[1, 2]], [[3, 4]])"
+    );
+}
+
+// ============================================================================
+// SPEC: standard library imports
+// ============================================================================
+
+#[test]
+fn test_inlined_helpers_bring_their_imports() {
+    let tokens = Lexer::new("=ROUND(A1,2)").tokenize().unwrap();
+    let expr = Parser::new(tokens).parse().unwrap();
+    let options = TranspileOptions {
+        inline_helpers: true,
+        ..Default::default()
+    };
+    let program = PythonEmitter::with_options(options)
+        .emit_program(&expr)
+        .unwrap();
+    assert!(program.starts_with("import decimal\nimport re\n\nclass ExcelError:"));
+    assert!(program.ends_with("\n\n\nround_(xl(\"A1\"), 2)"));
+}
+
+#[test]
+fn test_imported_helpers_need_no_imports() {
+    let tokens = Lexer::new("=INT(A1)").tokenize().unwrap();
+    let expr = Parser::new(tokens).parse().unwrap();
+    let program = PythonEmitter::new().emit_program(&expr).unwrap();
+    assert_eq!(program, "from expy_runtime import int_\n\nint_(xl(\"A1\"))");
+}
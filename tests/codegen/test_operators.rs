@@ -0,0 +1,283 @@
+use std::process::Command;
+
+use expy::TranspileOptions;
+use expy::bindings::lexer::Lexer;
+use expy::bindings::parser::Parser;
+use expy::codegen::python::PythonEmitter;
+
+/// Emits just the expression, with the helpers it needs
+fn emit(formula: &str) -> (String, Vec<&'static str>) {
+    let tokens = Lexer::new(formula).tokenize().unwrap();
+    let expr = Parser::new(tokens).parse().unwrap();
+    let mut emitter = PythonEmitter::new();
+    let python = emitter.emit(&expr).unwrap();
+    (python, emitter.helpers().collect())
+}
+
+fn python(formula: &str) -> String {
+    emit(formula).0
+}
+
+// ============================================================================
+// SPEC: arithmetic
+// ============================================================================
+
+#[test]
+fn test_addition() {
+    assert_eq!(python("=1+2"), "1 + 2");
+}
+
+#[test]
+fn test_subtraction_grouping() {
+    assert_eq!(python("=1-2-3"), "1 - 2 - 3");
+    assert_eq!(python("=1-(2-3)"), "1 - (2 - 3)");
+}
+
+#[test]
+fn test_multiplication_and_division() {
+    assert_eq!(python("=A1*2/4"), "to_number(xl(\"A1\")) * 2 / 4");
+    assert_eq!(python("=(1+2)*3"), "(1 + 2) * 3");
+}
+
+#[test]
+fn test_division_by_reference() {
+    let (python, helpers) = emit("=A1/B1");
+    assert_eq!(python, "divide(xl(\"A1\"), xl(\"B1\"))");
+    assert_eq!(helpers, ["divide"]);
+}
+
+#[test]
+fn test_division_by_zero_literal() {
+    assert_eq!(python("=1/0"), "divide(1, 0)");
+    assert_eq!(python("=1/-2"), "1 / -2");
+}
+
+#[test]
+fn test_decimal_numbers() {
+    assert_eq!(python("=2.5*1E+20"), "2.5 * 1e20");
+}
+
+#[test]
+fn test_power() {
+    assert_eq!(python("=2^3"), "2 ** 3");
+}
+
+#[test]
+fn test_power_left_associative() {
+    assert_eq!(python("=2^3^2"), "(2 ** 3) ** 2");
+    assert_eq!(python("=2^(3^2)"), "power(2, 3 ** 2)");
+}
+
+#[test]
+fn test_negated_base() {
+    assert_eq!(python("=-2^2"), "(-2) ** 2");
+}
+
+#[test]
+fn test_negative_exponent() {
+    assert_eq!(python("=2^-1"), "power(2, -1)");
+}
+
+#[test]
+fn test_negation() {
+    assert_eq!(python("=-A1"), "-to_number(xl(\"A1\"))");
+    assert_eq!(python("=-(1+2)"), "-(1 + 2)");
+    assert_eq!(python("=--A1"), "--to_number(xl(\"A1\"))");
+}
+
+#[test]
+fn test_unary_plus_dropped() {
+    assert_eq!(python("=+A1"), "xl(\"A1\")");
+}
+
+#[test]
+fn test_percent() {
+    assert_eq!(python("=50%"), "50 / 100");
+    assert_eq!(python("=A1*10%"), "to_number(xl(\"A1\")) * (10 / 100)");
+    assert_eq!(python("=(1+2)%"), "(1 + 2) / 100");
+}
+
+#[test]
+fn test_percent_power() {
+    assert_eq!(python("=2^50%"), "power(2, 50 / 100)");
+}
+
+// ============================================================================
+// SPEC: operand coercion - blanks count as 0, numeric text as its number
+// ============================================================================
+
+/// Runs a formula with its inlined helpers in python3 and returns the repr
+/// of the result, or `None` when no python3 is installed
+fn run(formula: &str, a1: &str) -> Option<String> {
+    let options = TranspileOptions {
+        inline_helpers: true,
+        ..Default::default()
+    };
+    let program = expy::transpile_with(formula, &options).unwrap();
+    let (helpers, expression) = program.rsplit_once('\n').unwrap();
+    let script = format!(
+        "{helpers}\ndef xl(ref):\n    return {{\"A1\": {a1}}}.get(ref)\nprint(repr({expression}))"
+    );
+    let output = Command::new("python3")
+        .arg("-c")
+        .arg(script)
+        .output()
+        .ok()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Some(String::from_utf8(output.stdout).unwrap().trim().to_string())
+}
+
+#[test]
+fn test_coerced_operands() {
+    let (python, helpers) = emit("=A1+1");
+    assert_eq!(python, "to_number(xl(\"A1\")) + 1");
+    assert_eq!(helpers, ["to_number"]);
+}
+
+#[test]
+fn test_coerced_text_and_logicals() {
+    assert_eq!(python("=A1%"), "to_number(xl(\"A1\")) / 100");
+    assert_eq!(python("=\"3\"*2"), "to_number(\"3\") * 2");
+    assert_eq!(python("=TRUE+1"), "to_number(True) + 1");
+}
+
+#[test]
+fn test_numbers_stay_bare() {
+    assert_eq!(python("=(1+2)*-3"), "(1 + 2) * -3");
+    assert_eq!(python("=-(A1*2)"), "-(to_number(xl(\"A1\")) * 2)");
+}
+
+#[test]
+fn test_blank_cell_is_zero() {
+    let Some(result) = run("=A1+1", "None") else {
+        return;
+    };
+    assert_eq!(result, "1");
+    assert_eq!(run("=-A1", "None").unwrap(), "0");
+    assert_eq!(run("=A1/2", "None").unwrap(), "0.0");
+    assert_eq!(run("=A1^2", "None").unwrap(), "0");
+    assert_eq!(run("=1/A1", "None").unwrap(), "ExcelError('#DIV/0!')");
+}
+
+#[test]
+fn test_numeric_text_is_its_number() {
+    let Some(result) = run("=A1+1", "\"3\"") else {
+        return;
+    };
+    assert_eq!(result, "4.0");
+    assert_eq!(run("=A1*A1", "\"3\"").unwrap(), "9.0");
+    assert_eq!(run("=6/A1", "\"3\"").unwrap(), "2.0");
+    assert_eq!(run("=A1^A1", "\"3\"").unwrap(), "27.0");
+    assert_eq!(run("=A1%", "\"3\"").unwrap(), "0.03");
+    assert_eq!(run("=A1+1", "\" 1e3 \"").unwrap(), "1001.0");
+    for text in ["x", "nan", "inf", "Infinity", "1_000", ""] {
+        let a1 = format!("\"{text}\"");
+        assert_eq!(
+            run("=A1+1", &a1).unwrap(),
+            "ExcelError('#VALUE!')",
+            "{text}"
+        );
+    }
+    assert_eq!(run("=\"inf\"+1", "None").unwrap(), "ExcelError('#VALUE!')");
+}
+
+// ============================================================================
+// SPEC: comparison
+// ============================================================================
+
+#[test]
+fn test_numeric_comparison() {
+    assert_eq!(python("=1+1=2"), "1 + 1 == 2");
+    assert_eq!(python("=1<>2"), "1 != 2");
+    assert_eq!(python("=1<=2"), "1 <= 2");
+}
+
+#[test]
+fn test_comparison_not_chained() {
+    assert_eq!(python("=1<2=TRUE"), "(1 < 2) == True");
+}
+
+#[test]
+fn test_text_equality_case_insensitive() {
+    let (python, helpers) = emit("=A1=\"yes\"");
+    assert_eq!(python, "compare(xl(\"A1\"), \"yes\") == 0");
+    assert_eq!(helpers, ["compare"]);
+}
+
+#[test]
+fn test_reference_comparisons() {
+    assert_eq!(python("=A1<>B1"), "compare(xl(\"A1\"), xl(\"B1\")) != 0");
+    assert_eq!(python("=A1>=0"), "compare(xl(\"A1\"), 0) >= 0");
+}
+
+// ============================================================================
+// SPEC: concatenation
+// ============================================================================
+
+#[test]
+fn test_concat_text() {
+    let (python, helpers) = emit("=\"a\"&\"b\"");
+    assert_eq!(python, "\"a\" + \"b\"");
+    assert!(helpers.is_empty());
+}
+
+#[test]
+fn test_concat_coerces() {
+    let (python, helpers) = emit("=A1&1");
+    assert_eq!(python, "concat(xl(\"A1\"), 1)");
+    assert_eq!(helpers, ["concat"]);
+}
+
+#[test]
+fn test_concat_flattened() {
+    assert_eq!(
+        python("=A1&\" \"&B1&\"!\""),
+        "concat(xl(\"A1\"), \" \", xl(\"B1\"), \"!\")"
+    );
+}
+
+#[test]
+fn test_concat_below_arithmetic() {
+    assert_eq!(python("=\"n=\"&1+2"), "concat(\"n=\", 1 + 2)");
+}
+
+#[test]
+fn test_string_escapes() {
+    assert_eq!(python("=\"say \"\"hi\"\"\""), "\"say \\\"hi\\\"\"");
+    assert_eq!(python("=\"C:\\temp\""), "\"C:\\\\temp\"");
+}
+
+// ============================================================================
+// SPEC: full program
+// ============================================================================
+
+#[test]
+fn test_program_imports_helpers() {
+    let python = expy::transpile("=A1&1").unwrap();
+    assert_eq!(
+        python,
+        "from expy_runtime import concat\n\nconcat(xl(\"A1\"), 1)"
+    );
+}
+
+#[test]
+fn test_program_inlines_helpers() {
+    let options = TranspileOptions {
+        inline_helpers: true,
+        ..Default::default()
+    };
+    let python = expy::transpile_with("=A1&1", &options).unwrap();
+    assert!(python.starts_with("class ExcelError:"));
+    assert!(python.contains("def to_text(value):"));
+    assert!(python.contains("def concat(*values):"));
+    assert!(python.ends_with("\n\n\nconcat(xl(\"A1\"), 1)"));
+}
+
+#[test]
+fn test_program_without_helpers() {
+    assert_eq!(expy::transpile("=1+2").unwrap(), "1 + 2");
+}
@@ -0,0 +1,428 @@
+use std::process::Command;
+
+use expy::bindings::lexer::Lexer;
+use expy::bindings::parser::Parser;
+use expy::codegen::python::PythonEmitter;
+use expy::codegen::{Backend, CodegenError, ReferenceStyle, TranspileOptions};
+
+fn emit(
+    formula: &str,
+    reference_style: ReferenceStyle,
+    backend: Backend,
+) -> Result<String, CodegenError> {
+    let tokens = Lexer::new(formula).tokenize().unwrap();
+    let expr = Parser::new(tokens).parse().unwrap();
+    let options = TranspileOptions {
+        reference_style,
+        backend,
+        ..Default::default()
+    };
+    PythonEmitter::with_options(options).emit_program(&expr)
+}
+
+fn python(formula: &str, reference_style: ReferenceStyle, backend: Backend) -> String {
+    emit(formula, reference_style, backend).unwrap()
+}
+
+/// Runs a formula with its inlined helpers in python3, with A1 and A2 set
+/// to the given Python values, and returns the repr of the result; `None`
+/// when no python3 is installed
+fn run(formula: &str, a1: &str, a2: &str) -> Option<String> {
+    let options = TranspileOptions {
+        inline_helpers: true,
+        ..Default::default()
+    };
+    let program = expy::transpile_with(formula, &options).unwrap();
+    let (helpers, expression) = program.rsplit_once('\n').unwrap();
+    let script = format!(
+        "{helpers}\ncells = {{\"A1\": {a1}, \"A2\": {a2}}}\n\
+         def xl(ref):\n    \
+             if \":\" in ref:\n        \
+                 return [[cells[\"A1\"]], [cells[\"A2\"]]]\n    \
+             return cells.get(ref)\n\
+         print(repr({expression}))"
+    );
+    let output = Command::new("python3")
+        .arg("-c")
+        .arg(script)
+        .output()
+        .ok()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Some(String::from_utf8(output.stdout).unwrap().trim().to_string())
+}
+
+// ============================================================================
+// SPEC: Backend::Python - ranges are nested lists
+// ============================================================================
+
+#[test]
+fn test_python_backend_is_default() {
+    assert_eq!(TranspileOptions::default().backend, Backend::Python);
+}
+
+#[test]
+fn test_function_range() {
+    assert_eq!(
+        python("=A1:B10", ReferenceStyle::Function, Backend::Python),
+        "xl(\"A1:B10\")"
+    );
+    assert_eq!(
+        python(
+            "=Sheet2!$A$1:B10",
+            ReferenceStyle::Function,
+            Backend::Python
+        ),
+        "xl(\"Sheet2!$A$1:B10\")"
+    );
+}
+
+#[test]
+fn test_function_whole_columns_and_rows() {
+    assert_eq!(
+        python("=A:A", ReferenceStyle::Function, Backend::Python),
+        "xl(\"A:A\")"
+    );
+    assert_eq!(
+        python("=2:3", ReferenceStyle::Function, Backend::Python),
+        "xl(\"2:3\")"
+    );
+}
+
+#[test]
+fn test_dict_range() {
+    assert_eq!(
+        python("=B2:C3", ReferenceStyle::Dict, Backend::Python),
+        "[[cells[\"Sheet1\"][column + str(row)] for column in [\"B\", \"C\"]] for row in range(2, 4)]"
+    );
+}
+
+#[test]
+fn test_openpyxl_range() {
+    assert_eq!(
+        python("=Data!$A$1:B10", ReferenceStyle::Openpyxl, Backend::Python),
+        "[[cell.value for cell in row] for row in wb[\"Data\"][\"A1:B10\"]]"
+    );
+}
+
+#[test]
+fn test_pandas_style_range_as_lists() {
+    assert_eq!(
+        python("=A1:B10", ReferenceStyle::Pandas, Backend::Python),
+        "df.iloc[0:10, 0:2].values.tolist()"
+    );
+}
+
+#[test]
+fn test_reversed_corners() {
+    assert_eq!(
+        python("=B10:A1", ReferenceStyle::Pandas, Backend::Python),
+        "df.iloc[0:10, 0:2].values.tolist()"
+    );
+}
+
+#[test]
+fn test_python_sum() {
+    assert_eq!(
+        python("=SUM(A1:B10)", ReferenceStyle::Function, Backend::Python),
+        "from expy_runtime import sum_\n\nsum_(xl(\"A1:B10\"))"
+    );
+}
+
+#[test]
+fn test_python_sum_cells_as_ranges() {
+    assert_eq!(
+        python(
+            "=SUM(A1,A1:A2,\"3\",Sheet2!B1)",
+            ReferenceStyle::Function,
+            Backend::Python
+        ),
+        "from expy_runtime import sum_\n\n\
+         sum_([[xl(\"A1\")]], xl(\"A1:A2\"), \"3\", [[xl(\"Sheet2!B1\")]])"
+    );
+}
+
+#[test]
+fn test_sum_skips_text_and_logical_cells() {
+    let Some(result) = run("=SUM(A1)", "\"x\"", "None") else {
+        return;
+    };
+    assert_eq!(result, "0");
+    assert_eq!(run("=SUM(A1)", "True", "None").unwrap(), "0");
+    assert_eq!(run("=SUM(A1,A2)", "\"3\"", "4").unwrap(), "4");
+    assert_eq!(run("=SUM(A1:A2,5)", "True", "2.5").unwrap(), "7.5");
+}
+
+#[test]
+fn test_sum_coerces_literals() {
+    let Some(result) = run("=SUM(A1,\"3\",TRUE)", "\"x\"", "None") else {
+        return;
+    };
+    assert_eq!(result, "4.0");
+    assert_eq!(
+        run("=SUM(A1,\"x\")", "1", "None").unwrap(),
+        "ExcelError('#VALUE!')"
+    );
+}
+
+#[test]
+fn test_python_range_arithmetic_unsupported() {
+    for formula in [
+        "=A1:A3+1", "=2*A1:A3", "=-A1:A3", "=A1:A3%", "=A1:A3^2", "={1,2}+1",
+    ] {
+        let err = emit(formula, ReferenceStyle::Function, Backend::Python).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "array arithmetic with the Python backend is not supported yet",
+            "{formula}"
+        );
+    }
+}
+
+// ============================================================================
+// SPEC: Backend::Numpy - ranges are np.ndarray values
+// ============================================================================
+
+#[test]
+fn test_numpy_pandas_style_slice() {
+    assert_eq!(
+        python("=SUM(A1:B10)", ReferenceStyle::Pandas, Backend::Numpy),
+        "import numpy as np\nfrom expy_runtime import range_numbers\n\n\
+         np.sum(range_numbers(df.iloc[0:10, 0:2].to_numpy()))"
+    );
+}
+
+#[test]
+fn test_numpy_wraps_lists() {
+    assert_eq!(
+        python("=A1:B2", ReferenceStyle::Function, Backend::Numpy),
+        "import numpy as np\n\nnp.array(xl(\"A1:B2\"))"
+    );
+}
+
+#[test]
+fn test_numpy_sum_several_arguments() {
+    assert_eq!(
+        python(
+            "=SUM(A:A,Sheet2!B1:B5,10)",
+            ReferenceStyle::Pandas,
+            Backend::Numpy
+        ),
+        "import numpy as np\nfrom expy_runtime import range_numbers\n\n\
+         np.sum(range_numbers(df.iloc[:, 0:1].to_numpy())) + np.sum(range_numbers(dfs[\"Sheet2\"].iloc[0:5, 1:2].to_numpy())) + 10"
+    );
+}
+
+#[test]
+fn test_numpy_sum_scalars_go_to_sum_() {
+    assert_eq!(
+        python(
+            "=SUM(A1:B2,C1,\"3\",TRUE,5)",
+            ReferenceStyle::Pandas,
+            Backend::Numpy
+        ),
+        "import numpy as np\nfrom expy_runtime import range_numbers, sum_\n\n\
+         np.sum(range_numbers(df.iloc[0:2, 0:2].to_numpy())) + 5 + sum_([[df.iat[0, 2]]], \"3\", True)"
+    );
+}
+
+#[test]
+fn test_numpy_sum_names_and_calls() {
+    assert_eq!(
+        python(
+            "=SUM(Sales[Amount],MyRange,INDEX(A1:B3,0,1))",
+            ReferenceStyle::Function,
+            Backend::Numpy
+        ),
+        "import numpy as np\nfrom expy_runtime import index, sum_\n\n\
+         sum_(xl(\"Sales[Amount]\"), xl(\"MyRange\"), index(np.array(xl(\"A1:B3\")), 0, 1))"
+    );
+    assert_eq!(
+        python(
+            "=SUM(TEXTSPLIT(\"1,2\",\",\"))",
+            ReferenceStyle::Function,
+            Backend::Numpy
+        ),
+        "from expy_runtime import sum_, textsplit\n\nsum_(textsplit(\"1,2\", \",\"))"
+    );
+}
+
+#[test]
+fn test_numpy_sum_range_arithmetic() {
+    assert_eq!(
+        python("=SUM(A1:A3*2)", ReferenceStyle::Function, Backend::Numpy),
+        "import numpy as np\nfrom expy_runtime import range_numbers\n\n\
+         np.sum(range_numbers(np.array(xl(\"A1:A3\")) * 2))"
+    );
+}
+
+#[test]
+fn test_numpy_sum_array_constant() {
+    assert_eq!(
+        python("=SUM({1,2},5)", ReferenceStyle::Pandas, Backend::Numpy),
+        "import numpy as np\nfrom expy_runtime import range_numbers\n\n\
+         np.sum(range_numbers(np.array([[1, 2]]))) + 5"
+    );
+}
+
+#[test]
+fn test_numpy_range_arithmetic() {
+    assert_eq!(
+        python("=A1:A3*2+B1", ReferenceStyle::Pandas, Backend::Numpy),
+        "from expy_runtime import to_number\n\n\
+         df.iloc[0:3, 0:1].to_numpy() * 2 + to_number(df.iat[0, 1])"
+    );
+    let err = emit("=A1:A3/B1", ReferenceStyle::Pandas, Backend::Numpy).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "array division or powers by anything but a literal is not supported yet"
+    );
+}
+
+#[test]
+fn test_numpy_sum_in_expression() {
+    assert_eq!(
+        python("=SUM(1+2)*2", ReferenceStyle::Pandas, Backend::Numpy),
+        "(1 + 2) * 2"
+    );
+}
+
+// ============================================================================
+// SPEC: Backend::Pandas - ranges are DataFrames
+// ============================================================================
+
+#[test]
+fn test_pandas_iloc() {
+    assert_eq!(
+        python("=Sheet2!C3:D4", ReferenceStyle::Pandas, Backend::Pandas),
+        "dfs[\"Sheet2\"].iloc[2:4, 2:4]"
+    );
+}
+
+#[test]
+fn test_pandas_whole_rows() {
+    assert_eq!(
+        python("=2:2", ReferenceStyle::Pandas, Backend::Pandas),
+        "df.iloc[1:2, :]"
+    );
+}
+
+#[test]
+fn test_pandas_sum() {
+    assert_eq!(
+        python("=SUM(A1:B10)", ReferenceStyle::Pandas, Backend::Pandas),
+        "from expy_runtime import range_numbers\n\n\
+         range_numbers(df.iloc[0:10, 0:2]).sum().sum()"
+    );
+}
+
+#[test]
+fn test_pandas_sum_scalars_go_to_sum_() {
+    assert_eq!(
+        python(
+            "=SUM(A1:B2,C1,\"3\",TRUE,5)",
+            ReferenceStyle::Pandas,
+            Backend::Pandas
+        ),
+        "from expy_runtime import range_numbers, sum_\n\n\
+         range_numbers(df.iloc[0:2, 0:2]).sum().sum() + 5 + sum_([[df.iat[0, 2]]], \"3\", True)"
+    );
+}
+
+#[test]
+fn test_pandas_sum_names_and_calls() {
+    assert_eq!(
+        python(
+            "=SUM(Sales[Amount],MyRange,INDEX(A1:B3,0,1))",
+            ReferenceStyle::Function,
+            Backend::Pandas
+        ),
+        "import pandas as pd\nfrom expy_runtime import index, sum_\n\n\
+         sum_(xl(\"Sales[Amount]\"), xl(\"MyRange\"), index(pd.DataFrame(xl(\"A1:B3\")), 0, 1))"
+    );
+    assert_eq!(
+        python(
+            "=SUM(TEXTSPLIT(\"1,2\",\",\"))",
+            ReferenceStyle::Pandas,
+            Backend::Pandas
+        ),
+        "from expy_runtime import sum_, textsplit\n\nsum_(textsplit(\"1,2\", \",\"))"
+    );
+}
+
+#[test]
+fn test_pandas_sum_range_arithmetic() {
+    assert_eq!(
+        python("=SUM(A1:A3*2)", ReferenceStyle::Pandas, Backend::Pandas),
+        "from expy_runtime import range_numbers\n\n\
+         range_numbers(df.iloc[0:3, 0:1] * 2).sum().sum()"
+    );
+}
+
+#[test]
+fn test_pandas_wraps_lists() {
+    assert_eq!(
+        python("=SUM(A1:B2)", ReferenceStyle::Function, Backend::Pandas),
+        "import pandas as pd\nfrom expy_runtime import range_numbers\n\n\
+         range_numbers(pd.DataFrame(xl(\"A1:B2\"))).sum().sum()"
+    );
+}
+
+// ============================================================================
+// SPEC: INDEX as a range end
+// ============================================================================
+
+#[test]
+fn test_range_from_index() {
+    assert_eq!(
+        python(
+            "=INDEX(A:A,3):B5",
+            ReferenceStyle::Function,
+            Backend::Python
+        ),
+        "xl(\"A3:B5\")"
+    );
+    assert_eq!(
+        python("=INDEX(A:A,3):B5", ReferenceStyle::Pandas, Backend::Python),
+        "df.iloc[2:5, 0:2].values.tolist()"
+    );
+}
+
+#[test]
+fn test_range_to_index() {
+    assert_eq!(
+        python(
+            "=Sheet2!A1:INDEX(Sheet2!A:A,3)",
+            ReferenceStyle::Function,
+            Backend::Python
+        ),
+        "xl(\"Sheet2!A1:A3\")"
+    );
+}
+
+// ============================================================================
+// SPEC: ranges a style cannot express
+// ============================================================================
+
+#[test]
+fn test_whole_column_needs_pandas_or_function() {
+    assert!(matches!(
+        emit("=A:A", ReferenceStyle::Dict, Backend::Python),
+        Err(CodegenError::Unsupported(_))
+    ));
+}
+
+#[test]
+fn test_range_from_dynamic_index_unsupported() {
+    assert!(matches!(
+        emit(
+            "=INDEX(A:A,B1):B5",
+            ReferenceStyle::Function,
+            Backend::Python
+        ),
+        Err(CodegenError::Unsupported(_))
+    ));
+}
@@ -0,0 +1,161 @@
+use expy::bindings::lexer::Lexer;
+use expy::bindings::parser::Parser;
+use expy::codegen::python::PythonEmitter;
+use expy::codegen::{CodegenError, ReferenceStyle, TranspileOptions};
+
+fn emit(formula: &str, reference_style: ReferenceStyle) -> Result<String, CodegenError> {
+    let tokens = Lexer::new(formula).tokenize().unwrap();
+    let expr = Parser::new(tokens).parse().unwrap();
+    let options = TranspileOptions {
+        reference_style,
+        ..Default::default()
+    };
+    PythonEmitter::with_options(options).emit(&expr)
+}
+
+fn python(formula: &str, reference_style: ReferenceStyle) -> String {
+    emit(formula, reference_style).unwrap()
+}
+
+// ============================================================================
+// SPEC: ReferenceStyle::Function - xl("A1")
+// ============================================================================
+
+#[test]
+fn test_function_style_is_default() {
+    assert_eq!(
+        TranspileOptions::default().reference_style,
+        ReferenceStyle::Function
+    );
+}
+
+#[test]
+fn test_function_cells() {
+    assert_eq!(
+        python("=A1*Sheet2!B3", ReferenceStyle::Function),
+        "to_number(xl(\"A1\")) * to_number(xl(\"Sheet2!B3\"))"
+    );
+}
+
+#[test]
+fn test_function_keeps_reference_text() {
+    assert_eq!(python("=$A$1", ReferenceStyle::Function), "xl(\"$A$1\")");
+    assert_eq!(
+        python("='Q1 Budget'!B4", ReferenceStyle::Function),
+        "xl(\"'Q1 Budget'!B4\")"
+    );
+    assert_eq!(
+        python("=TaxRate", ReferenceStyle::Function),
+        "xl(\"TaxRate\")"
+    );
+}
+
+// ============================================================================
+// SPEC: ReferenceStyle::Dict - cells["Sheet1"]["A1"]
+// ============================================================================
+
+#[test]
+fn test_dict_cells() {
+    assert_eq!(
+        python("=A1*Sheet2!B3", ReferenceStyle::Dict),
+        "to_number(cells[\"Sheet1\"][\"A1\"]) * to_number(cells[\"Sheet2\"][\"B3\"])"
+    );
+}
+
+#[test]
+fn test_dict_drops_absolute_markers() {
+    assert_eq!(
+        python("=$C$10", ReferenceStyle::Dict),
+        "cells[\"Sheet1\"][\"C10\"]"
+    );
+}
+
+#[test]
+fn test_dict_current_sheet() {
+    let tokens = Lexer::new("=A1").tokenize().unwrap();
+    let expr = Parser::new(tokens).parse().unwrap();
+    let options = TranspileOptions {
+        reference_style: ReferenceStyle::Dict,
+        sheet: "Summary".into(),
+        ..Default::default()
+    };
+    let python = PythonEmitter::with_options(options).emit(&expr).unwrap();
+    assert_eq!(python, "cells[\"Summary\"][\"A1\"]");
+}
+
+#[test]
+fn test_dict_names() {
+    assert_eq!(
+        python("=TaxRate", ReferenceStyle::Dict),
+        "names[\"TaxRate\"]"
+    );
+}
+
+// ============================================================================
+// SPEC: ReferenceStyle::Openpyxl - ws["A1"].value
+// ============================================================================
+
+#[test]
+fn test_openpyxl_cells() {
+    assert_eq!(
+        python("=A1*Sheet2!B3", ReferenceStyle::Openpyxl),
+        "to_number(ws[\"A1\"].value) * to_number(wb[\"Sheet2\"][\"B3\"].value)"
+    );
+}
+
+#[test]
+fn test_openpyxl_quoted_sheet() {
+    assert_eq!(
+        python("='Q1 Budget'!$B$4", ReferenceStyle::Openpyxl),
+        "wb[\"Q1 Budget\"][\"B4\"].value"
+    );
+}
+
+// ============================================================================
+// SPEC: ReferenceStyle::Pandas - df.iat[0, 0]
+// ============================================================================
+
+#[test]
+fn test_pandas_cells() {
+    assert_eq!(
+        python("=A1*Sheet2!B3", ReferenceStyle::Pandas),
+        "to_number(df.iat[0, 0]) * to_number(dfs[\"Sheet2\"].iat[2, 1])"
+    );
+}
+
+#[test]
+fn test_pandas_negation() {
+    assert_eq!(
+        python("=-C5", ReferenceStyle::Pandas),
+        "-to_number(df.iat[4, 2])"
+    );
+}
+
+// ============================================================================
+// SPEC: references a style cannot express
+// ============================================================================
+
+#[test]
+fn test_external_workbook_unsupported() {
+    assert!(matches!(
+        emit("=[1]Prices!A1", ReferenceStyle::Dict),
+        Err(CodegenError::Unsupported(_))
+    ));
+}
+
+#[test]
+fn test_multiple_sheets_unsupported() {
+    assert!(matches!(
+        emit("=Jan:Dec!C3", ReferenceStyle::Openpyxl),
+        Err(CodegenError::Unsupported(_))
+    ));
+}
+
+#[test]
+fn test_names_unsupported_in_pandas() {
+    let err = emit("=TaxRate", ReferenceStyle::Pandas).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "TaxRate with the Pandas reference style is not supported yet"
+    );
+}
@@ -0,0 +1,158 @@
+use expy::bindings::lexer::Lexer;
+use expy::bindings::parser::Parser;
+use expy::codegen::CodegenError;
+use expy::codegen::python::PythonEmitter;
+
+fn try_emit(formula: &str) -> Result<(String, Vec<&'static str>), CodegenError> {
+    let tokens = Lexer::new(formula).tokenize().unwrap();
+    let expr = Parser::new(tokens).parse().unwrap();
+    let mut emitter = PythonEmitter::new();
+    let python = emitter.emit(&expr)?;
+    Ok((python, emitter.helpers().collect()))
+}
+
+fn emit(formula: &str) -> (String, Vec<&'static str>) {
+    try_emit(formula).unwrap()
+}
+
+fn python(formula: &str) -> String {
+    emit(formula).0
+}
+
+// ============================================================================
+// SPEC: text functions call runtime helpers
+// ============================================================================
+
+#[test]
+fn test_left_right_mid() {
+    let (left, helpers) = emit("=LEFT(A1,5)");
+    assert_eq!(left, "left(xl(\"A1\"), 5)");
+    assert_eq!(helpers, ["left"]);
+    assert_eq!(python("=RIGHT(A1)"), "right(xl(\"A1\"))");
+    assert_eq!(python("=MID(A1,7,3)"), "mid(xl(\"A1\"), 7, 3)");
+}
+
+#[test]
+fn test_find_and_search() {
+    assert_eq!(python("=FIND(\"o\",A1,6)"), "find(\"o\", xl(\"A1\"), 6)");
+    assert_eq!(python("=SEARCH(\"w?r\",A1)"), "search(\"w?r\", xl(\"A1\"))");
+}
+
+#[test]
+fn test_substitute_and_trim() {
+    assert_eq!(
+        python("=SUBSTITUTE(A1,\"o\",\"0\",2)"),
+        "substitute(xl(\"A1\"), \"o\", \"0\", 2)"
+    );
+    assert_eq!(python("=TRIM(A1)"), "trim(xl(\"A1\"))");
+}
+
+#[test]
+fn test_textjoin_ranges() {
+    assert_eq!(
+        python("=TEXTJOIN(\", \",TRUE,A1:A3,\"z\")"),
+        "textjoin(\", \", True, xl(\"A1:A3\"), \"z\")"
+    );
+}
+
+#[test]
+fn test_textsplit() {
+    assert_eq!(
+        python("=TEXTSPLIT(A1,{\",\",\";\"})"),
+        "textsplit(xl(\"A1\"), [[\",\", \";\"]])"
+    );
+}
+
+// ============================================================================
+// SPEC: empty text arguments are empty text
+// ============================================================================
+
+#[test]
+fn test_missing_text_argument() {
+    // Only rows: an omitted column delimiter must not split on "0"
+    assert_eq!(
+        python("=TEXTSPLIT(A1,,\",\")"),
+        "textsplit(xl(\"A1\"), \"\", \",\")"
+    );
+    assert_eq!(
+        python("=SUBSTITUTE(A1,\"x\",)"),
+        "substitute(xl(\"A1\"), \"x\", \"\")"
+    );
+    // Numeric arguments still pass 0
+    assert_eq!(python("=LEFT(A1,)"), "left(xl(\"A1\"), 0)");
+}
+
+// ============================================================================
+// SPEC: string literals are escaped for Python
+// ============================================================================
+
+#[test]
+fn test_escaped_arguments() {
+    assert_eq!(
+        python("=SUBSTITUTE(A1,\"\"\"\",\"\\\")"),
+        "substitute(xl(\"A1\"), \"\\\"\", \"\\\\\")"
+    );
+    assert_eq!(
+        python("=FIND(\"\"\"a\"\"\",A1)"),
+        "find(\"\\\"a\\\"\", xl(\"A1\"))"
+    );
+}
+
+// ============================================================================
+// SPEC: TEXT formats are parsed when transpiling
+// ============================================================================
+
+#[test]
+fn test_text_number_format() {
+    let (text, helpers) = emit("=TEXT(A1,\"$#,##0.00\")");
+    assert_eq!(
+        text,
+        "text_format(xl(\"A1\"), [(None, 0, [(\"text\", \"$\"), (\"integer\", \"#,##0\"), \
+         (\"fraction\", \"00\")])])"
+    );
+    assert_eq!(helpers, ["text_format"]);
+    assert_eq!(
+        python("=TEXT(A1,\"0%\")"),
+        "text_format(xl(\"A1\"), [(None, 2, [(\"integer\", \"0\"), (\"text\", \"%\")])])"
+    );
+}
+
+#[test]
+fn test_text_date_format() {
+    assert_eq!(
+        python("=TEXT(B2,\"yyyy-mm-dd\")"),
+        "text_format(xl(\"B2\"), [(None, 0, [(\"year\", 4), (\"text\", \"-\"), \
+         (\"month\", 2), (\"text\", \"-\"), (\"day\", 2)])])"
+    );
+    assert_eq!(
+        python("=TEXT(B2,\"h:mm AM/PM\")"),
+        "text_format(xl(\"B2\"), [(None, 0, [(\"hour\", 1), (\"text\", \":\"), \
+         (\"minute\", 2), (\"text\", \" \"), (\"ampm\", \"AM/PM\")])])"
+    );
+}
+
+#[test]
+fn test_text_sections() {
+    assert_eq!(
+        python("=TEXT(A1,\"[>=100][Red]0;\"\"x: \"\"@\")"),
+        "text_format(xl(\"A1\"), [((\">=\", 100), 0, [(\"integer\", \"0\")])], \
+         [(\"text\", \"x: \"), (\"value\", None)])"
+    );
+}
+
+#[test]
+fn test_text_invalid_format() {
+    assert_eq!(python("=TEXT(A1,\"[Mauve]0\")"), "ExcelError(\"#VALUE!\")");
+}
+
+#[test]
+fn test_text_unsupported_formats() {
+    assert!(matches!(
+        try_emit("=TEXT(A1,B1)"),
+        Err(CodegenError::Unsupported(_))
+    ));
+    assert!(matches!(
+        try_emit("=TEXT(A1,\"# ?/?\")"),
+        Err(CodegenError::Unsupported(_))
+    ));
+}
@@ -0,0 +1,12 @@
+mod codegen {
+    mod test_arrays;
+    mod test_criteria;
+    mod test_errors;
+    mod test_logical;
+    mod test_lookup;
+    mod test_math;
+    mod test_operators;
+    mod test_ranges;
+    mod test_references;
+    mod test_text;
+}
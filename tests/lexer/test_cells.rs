@@ -0,0 +1,140 @@
+use expy::bindings::lexer::Lexer;
+use expy::bindings::token::{CellRef, LexerError, Token};
+
+// ============================================================================
+// SPEC: CELL - $? [A-Z]+ $? [1-9][0-9]*
+// ============================================================================
+
+#[test]
+fn test_cell_simple() {
+    let mut lexer = Lexer::new("A1");
+    let tokens = lexer.tokenize().unwrap();
+    assert_eq!(tokens[0].value, Token::Cell(CellRef::new(1, 1)));
+    assert!(matches!(tokens[1].value, Token::Eof));
+}
+
+#[test]
+fn test_cell_lowercase() {
+    let mut lexer = Lexer::new("b3");
+    let tokens = lexer.tokenize().unwrap();
+    assert_eq!(tokens[0].value, Token::Cell(CellRef::new(2, 3)));
+}
+
+#[test]
+fn test_cell_absolute_column() {
+    let mut lexer = Lexer::new("$A1");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(
+        tokens[0].value,
+        Token::Cell(CellRef {
+            column: 1,
+            row: 1,
+            absolute_column: true,
+            absolute_row: false,
+        })
+    ));
+}
+
+#[test]
+fn test_cell_absolute_row() {
+    let mut lexer = Lexer::new("A$1");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(
+        tokens[0].value,
+        Token::Cell(CellRef {
+            column: 1,
+            row: 1,
+            absolute_column: false,
+            absolute_row: true,
+        })
+    ));
+}
+
+#[test]
+fn test_cell_fully_absolute() {
+    let mut lexer = Lexer::new("$B$2");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(
+        tokens[0].value,
+        Token::Cell(CellRef {
+            column: 2,
+            row: 2,
+            absolute_column: true,
+            absolute_row: true,
+        })
+    ));
+}
+
+#[test]
+fn test_cell_multi_letter_columns() {
+    let mut lexer = Lexer::new("AA1, ZZ100, XFD1048576");
+    let tokens = lexer.tokenize().unwrap();
+    assert_eq!(tokens[0].value, Token::Cell(CellRef::new(27, 1)));
+    assert_eq!(tokens[2].value, Token::Cell(CellRef::new(702, 100)));
+    assert_eq!(
+        tokens[4].value,
+        Token::Cell(CellRef::new(16_384, 1_048_576))
+    );
+}
+
+#[test]
+fn test_cell_in_expression() {
+    let mut lexer = Lexer::new("=A1+$B$2");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[0].value, Token::Equal));
+    assert_eq!(tokens[1].value, Token::Cell(CellRef::new(1, 1)));
+    assert!(matches!(tokens[2].value, Token::Plus));
+    assert!(matches!(
+        tokens[3].value,
+        Token::Cell(CellRef {
+            column: 2,
+            row: 2,
+            ..
+        })
+    ));
+    assert!(matches!(tokens[4].value, Token::Eof));
+}
+
+#[test]
+fn test_cell_display_round_trip() {
+    for text in ["A1", "$A1", "A$1", "$XFD$1048576", "AB12"] {
+        let mut lexer = Lexer::new(text);
+        let tokens = lexer.tokenize().unwrap();
+        match &tokens[0].value {
+            Token::Cell(cell) => assert_eq!(cell.to_string(), text),
+            other => panic!("expected cell, got {other:?}"),
+        }
+    }
+}
+
+// ============================================================================
+// Excel sheet limits: XFD columns, 1048576 rows
+// ============================================================================
+
+#[test]
+fn test_cell_column_out_of_range() {
+    let mut lexer = Lexer::new("$XFE1");
+    let result = lexer.tokenize();
+    assert!(matches!(result, Err(LexerError::InvalidCellReference(s, _)) if s == "$XFE1"));
+}
+
+#[test]
+fn test_cell_row_out_of_range() {
+    let mut lexer = Lexer::new("A$1048577");
+    let result = lexer.tokenize();
+    assert!(matches!(result, Err(LexerError::InvalidCellReference(s, _)) if s == "A$1048577"));
+}
+
+#[test]
+fn test_cell_row_zero() {
+    let mut lexer = Lexer::new("$A$0");
+    let result = lexer.tokenize();
+    assert!(matches!(result, Err(LexerError::InvalidCellReference(s, _)) if s == "$A$0"));
+}
+
+#[test]
+fn test_dollar_without_cell() {
+    let mut lexer = Lexer::new("$");
+    let result = lexer.tokenize();
+    assert!(matches!(result, Err(LexerError::UnexpectedChar('$', _))));
+}
@@ -10,7 +10,7 @@ fn test_empty_input() {
     let mut lexer = Lexer::new("");
     let tokens = lexer.tokenize().unwrap();
     assert_eq!(tokens.len(), 1);
-    assert!(matches!(tokens[0], Token::Eof));
+    assert!(matches!(tokens[0].value, Token::Eof));
 }
 
 #[test]
@@ -18,5 +18,5 @@ fn test_whitespace_only() {
     let mut lexer = Lexer::new("   \t\n  ");
     let tokens = lexer.tokenize().unwrap();
     assert_eq!(tokens.len(), 1);
-    assert!(matches!(tokens[0], Token::Eof));
+    assert!(matches!(tokens[0].value, Token::Eof));
 }
@@ -0,0 +1,125 @@
+use expy::bindings::lexer::Lexer;
+use expy::bindings::token::{CellRef, LexerError, Token};
+
+// ============================================================================
+// SPEC: FILE - \[ [0-9]+ \]
+// ============================================================================
+
+#[test]
+fn test_file_reference() {
+    let mut lexer = Lexer::new("[2]Prices!$B$3");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[0].value, Token::File(2)));
+    assert!(matches!(&tokens[1].value, Token::Sheet(s) if s == "Prices"));
+    assert!(matches!(tokens[2].value, Token::Exclamation));
+    assert!(matches!(
+        tokens[3].value,
+        Token::Cell(CellRef {
+            column: 2,
+            row: 3,
+            ..
+        })
+    ));
+    assert!(matches!(tokens[4].value, Token::Eof));
+}
+
+#[test]
+fn test_file_reference_quoted_sheet() {
+    let mut lexer = Lexer::new("'[1]Q1 Budget'!A1");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[0].value, Token::File(1)));
+    assert!(matches!(&tokens[1].value, Token::SheetQuoted(s) if s == "Q1 Budget"));
+    assert!(matches!(tokens[2].value, Token::Exclamation));
+    assert_eq!(tokens[3].value, Token::Cell(CellRef::new(1, 1)));
+}
+
+#[test]
+fn test_file_reference_multiple_sheets_quoted() {
+    let mut lexer = Lexer::new("'[3]Jan:Dec'!C3");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[0].value, Token::File(3)));
+    assert!(
+        matches!(&tokens[1].value, Token::MultipleSheetsQuoted(a, b) if a == "Jan" && b == "Dec")
+    );
+}
+
+#[test]
+fn test_file_reference_workbook_name() {
+    let mut lexer = Lexer::new("[1]!TaxRate");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[0].value, Token::File(1)));
+    assert!(matches!(tokens[1].value, Token::Exclamation));
+    assert!(matches!(&tokens[2].value, Token::NamedRange(n) if n == "TaxRate"));
+}
+
+#[test]
+fn test_file_reference_in_expression() {
+    let mut lexer = Lexer::new("=A1*[12]Rates!B2");
+    let tokens = lexer.tokenize().unwrap();
+    assert_eq!(tokens[1].value, Token::Cell(CellRef::new(1, 1)));
+    assert!(matches!(tokens[2].value, Token::Multiply));
+    assert!(matches!(tokens[3].value, Token::File(12)));
+    assert!(matches!(&tokens[4].value, Token::Sheet(s) if s == "Rates"));
+}
+
+#[test]
+fn test_bracketed_name_is_structured_ref() {
+    let mut lexer = Lexer::new("[1a]");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(
+        matches!(&tokens[0].value, Token::StructuredRef(r) if r.column.as_deref() == Some("1a"))
+    );
+}
+
+// ============================================================================
+// SPEC: DDECALL - application|'topic'!item
+// ============================================================================
+
+#[test]
+fn test_dde_call() {
+    let mut lexer = Lexer::new("=MSEXCEL|'Sheet1'!R1C1");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[0].value, Token::Equal));
+    assert!(matches!(
+        &tokens[1].value,
+        Token::DdeCall { application, topic, item: Some(item) }
+            if application == "MSEXCEL" && topic == "Sheet1" && item == "R1C1"
+    ));
+    assert!(matches!(tokens[2].value, Token::Eof));
+}
+
+#[test]
+fn test_dde_call_without_item() {
+    let mut lexer = Lexer::new("Server|'Topic'");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(
+        &tokens[0].value,
+        Token::DdeCall { application, topic, item: None }
+            if application == "Server" && topic == "Topic"
+    ));
+}
+
+#[test]
+fn test_dde_call_with_quotes() {
+    let mut lexer = Lexer::new("Feed|'Bob''s Topic'!'Last Price'");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(
+        &tokens[0].value,
+        Token::DdeCall { topic, item: Some(item), .. }
+            if topic == "Bob's Topic" && item == "Last Price"
+    ));
+}
+
+#[test]
+fn test_dde_call_unterminated() {
+    let mut lexer = Lexer::new("MSEXCEL|'Sheet1");
+    let result = lexer.tokenize();
+    assert!(matches!(result, Err(LexerError::UnterminatedDdeCall(_))));
+}
+
+#[test]
+fn test_dde_call_missing_topic_quote() {
+    let mut lexer = Lexer::new("MSEXCEL|Sheet1");
+    let result = lexer.tokenize();
+    assert!(matches!(result, Err(LexerError::UnexpectedChar('S', _))));
+}
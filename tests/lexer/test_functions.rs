@@ -0,0 +1,166 @@
+use expy::bindings::lexer::Lexer;
+use expy::bindings::token::{CellRef, Token};
+
+// ============================================================================
+// SPEC: EXCEL-FUNCTION - built-in function name followed by (
+// ============================================================================
+
+#[test]
+fn test_function_sum() {
+    let mut lexer = Lexer::new("SUM(A1:A10)");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::ExcelFunction(f) if f == "SUM"));
+    assert_eq!(tokens[1].value, Token::Cell(CellRef::new(1, 1)));
+    assert!(matches!(tokens[2].value, Token::Colon));
+    assert_eq!(tokens[3].value, Token::Cell(CellRef::new(1, 10)));
+    assert!(matches!(tokens[4].value, Token::RightParen));
+    assert!(matches!(tokens[5].value, Token::Eof));
+}
+
+#[test]
+fn test_function_name_is_canonicalized() {
+    let mut lexer = Lexer::new("average( Vlookup(");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::ExcelFunction(f) if f == "AVERAGE"));
+    assert!(matches!(&tokens[1].value, Token::ExcelFunction(f) if f == "VLOOKUP"));
+}
+
+#[test]
+fn test_function_with_dots_and_digits() {
+    let mut lexer = Lexer::new("CEILING.MATH( LOG10( ATAN2(");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::ExcelFunction(f) if f == "CEILING.MATH"));
+    assert!(matches!(&tokens[1].value, Token::ExcelFunction(f) if f == "LOG10"));
+    assert!(matches!(&tokens[2].value, Token::ExcelFunction(f) if f == "ATAN2"));
+}
+
+#[test]
+fn test_function_named_like_boolean() {
+    let mut lexer = Lexer::new("TRUE() TRUE");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::ExcelFunction(f) if f == "TRUE"));
+    assert!(matches!(tokens[1].value, Token::RightParen));
+    assert!(matches!(tokens[2].value, Token::Bool(true)));
+}
+
+#[test]
+fn test_function_future_prefix() {
+    let mut lexer = Lexer::new("_xlfn.XLOOKUP(");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::ExcelFunction(f) if f == "XLOOKUP"));
+}
+
+#[test]
+fn test_function_space_before_paren_is_not_call() {
+    let mut lexer = Lexer::new("SUM (");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::NamedRange(n) if n == "SUM"));
+    assert!(matches!(tokens[1].value, Token::Intersect));
+    assert!(matches!(tokens[2].value, Token::LeftParen));
+}
+
+#[test]
+fn test_function_nested_with_arguments() {
+    let mut lexer = Lexer::new("=ROUND(SUM(1,2),0)");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[0].value, Token::Equal));
+    assert!(matches!(&tokens[1].value, Token::ExcelFunction(f) if f == "ROUND"));
+    assert!(matches!(&tokens[2].value, Token::ExcelFunction(f) if f == "SUM"));
+    assert!(matches!(tokens[3].value, Token::Number(n) if n == 1.0));
+    assert!(matches!(tokens[4].value, Token::Comma));
+    assert!(matches!(tokens[5].value, Token::Number(n) if n == 2.0));
+    assert!(matches!(tokens[6].value, Token::RightParen));
+    assert!(matches!(tokens[7].value, Token::Comma));
+    assert!(matches!(tokens[8].value, Token::Number(n) if n == 0.0));
+    assert!(matches!(tokens[9].value, Token::RightParen));
+}
+
+// ============================================================================
+// SPEC: REF-FUNCTION - (INDEX | OFFSET | INDIRECT)(
+// ============================================================================
+
+#[test]
+fn test_ref_functions() {
+    let mut lexer = Lexer::new("INDEX( offset( Indirect(");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::RefFunction(f) if f == "INDEX"));
+    assert!(matches!(&tokens[1].value, Token::RefFunction(f) if f == "OFFSET"));
+    assert!(matches!(&tokens[2].value, Token::RefFunction(f) if f == "INDIRECT"));
+}
+
+// ============================================================================
+// SPEC: REF-FUNCTION-COND - (IF | CHOOSE)(
+// ============================================================================
+
+#[test]
+fn test_ref_function_cond() {
+    let mut lexer = Lexer::new("IF( choose(");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::RefFunctionCond(f) if f == "IF"));
+    assert!(matches!(&tokens[1].value, Token::RefFunctionCond(f) if f == "CHOOSE"));
+}
+
+#[test]
+fn test_iferror_is_ordinary_function() {
+    let mut lexer = Lexer::new("IFERROR(");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::ExcelFunction(f) if f == "IFERROR"));
+}
+
+// ============================================================================
+// SPEC: UDF - (_xll\.)? [A-Z_\][A-Z0-9_\\.★1]* (
+// ============================================================================
+
+#[test]
+fn test_udf_simple() {
+    let mut lexer = Lexer::new("MyMacro(1)");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::Udf(f) if f == "MyMacro"));
+    assert!(matches!(tokens[1].value, Token::Number(n) if n == 1.0));
+    assert!(matches!(tokens[2].value, Token::RightParen));
+}
+
+#[test]
+fn test_udf_xll() {
+    let mut lexer = Lexer::new("_xll.Foo(A1)");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::Udf(f) if f == "_xll.Foo"));
+    assert_eq!(tokens[1].value, Token::Cell(CellRef::new(1, 1)));
+}
+
+#[test]
+fn test_udf_underscore_and_backslash() {
+    let mut lexer = Lexer::new("_calc_total( \\helper(");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::Udf(f) if f == "_calc_total"));
+    assert!(matches!(&tokens[1].value, Token::Udf(f) if f == "\\helper"));
+}
+
+// ============================================================================
+// SPEC: ( ) - grouping parentheses
+// ============================================================================
+
+#[test]
+fn test_parentheses() {
+    let mut lexer = Lexer::new("(1+2)*3");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[0].value, Token::LeftParen));
+    assert!(matches!(tokens[1].value, Token::Number(n) if n == 1.0));
+    assert!(matches!(tokens[2].value, Token::Plus));
+    assert!(matches!(tokens[3].value, Token::Number(n) if n == 2.0));
+    assert!(matches!(tokens[4].value, Token::RightParen));
+    assert!(matches!(tokens[5].value, Token::Multiply));
+}
+
+#[test]
+fn test_sum_whole_column_and_row() {
+    let mut lexer = Lexer::new("=SUM(A:A)+SUM(2:2)");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[1].value, Token::ExcelFunction(f) if f == "SUM"));
+    assert!(matches!(tokens[2].value, Token::VerticalRange(..)));
+    assert!(matches!(tokens[3].value, Token::RightParen));
+    assert!(matches!(tokens[4].value, Token::Plus));
+    assert!(matches!(&tokens[5].value, Token::ExcelFunction(f) if f == "SUM"));
+    assert!(matches!(tokens[6].value, Token::HorizontalRange(..)));
+    assert!(matches!(tokens[7].value, Token::RightParen));
+}
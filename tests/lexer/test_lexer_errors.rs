@@ -9,40 +9,33 @@ use expy::bindings::token::LexerError;
 fn test_unexpected_char_at() {
     let mut lexer = Lexer::new("@");
     let result = lexer.tokenize();
-    assert!(matches!(result, Err(LexerError::UnexpectedChar('@'))));
+    assert!(matches!(result, Err(LexerError::UnexpectedChar('@', _))));
 }
 
 #[test]
 fn test_unexpected_char_in_expression() {
     let mut lexer = Lexer::new("1 + @ - 2");
     let result = lexer.tokenize();
-    assert!(matches!(result, Err(LexerError::UnexpectedChar('@'))));
+    assert!(matches!(result, Err(LexerError::UnexpectedChar('@', _))));
 }
 
 #[test]
 fn test_unterminated_string() {
     let mut lexer = Lexer::new(r#""hello"#);
     let result = lexer.tokenize();
-    assert!(matches!(result, Err(LexerError::UnterminatedString)));
+    assert!(matches!(result, Err(LexerError::UnterminatedString(_))));
 }
 
 #[test]
 fn test_unterminated_string_with_escape() {
     let mut lexer = Lexer::new(r#""hello""world"#);
     let result = lexer.tokenize();
-    assert!(matches!(result, Err(LexerError::UnterminatedString)));
+    assert!(matches!(result, Err(LexerError::UnterminatedString(_))));
 }
 
 #[test]
-fn test_unknown_identifier() {
-    let mut lexer = Lexer::new("foo");
+fn test_unexpected_char_question_mark() {
+    let mut lexer = Lexer::new("1 + ?");
     let result = lexer.tokenize();
-    assert!(matches!(result, Err(LexerError::UnexpectedChar('f'))));
-}
-
-#[test]
-fn test_unknown_identifier_mixed() {
-    let mut lexer = Lexer::new("1 + bar - 2");
-    let result = lexer.tokenize();
-    assert!(matches!(result, Err(LexerError::UnexpectedChar('b'))));
+    assert!(matches!(result, Err(LexerError::UnexpectedChar('?', _))));
 }
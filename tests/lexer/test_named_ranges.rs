@@ -0,0 +1,130 @@
+use expy::bindings::lexer::Lexer;
+use expy::bindings::token::{CellRef, LexerError, Token};
+
+// ============================================================================
+// SPEC: NR - [A-Z_\\★1][★4]*
+// ============================================================================
+
+#[test]
+fn test_named_range_simple() {
+    let mut lexer = Lexer::new("TaxRate");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::NamedRange(n) if n == "TaxRate"));
+    assert!(matches!(tokens[1].value, Token::Eof));
+}
+
+#[test]
+fn test_named_range_in_expression() {
+    let mut lexer = Lexer::new("1 + bar - 2");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[0].value, Token::Number(n) if n == 1.0));
+    assert!(matches!(tokens[1].value, Token::Plus));
+    assert!(matches!(&tokens[2].value, Token::NamedRange(n) if n == "bar"));
+    assert!(matches!(tokens[3].value, Token::Minus));
+    assert!(matches!(tokens[4].value, Token::Number(n) if n == 2.0));
+}
+
+#[test]
+fn test_named_range_with_underscores() {
+    let mut lexer = Lexer::new("_total, gross_margin_2024");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::NamedRange(n) if n == "_total"));
+    assert!(matches!(&tokens[2].value, Token::NamedRange(n) if n == "gross_margin_2024"));
+}
+
+#[test]
+fn test_named_range_with_dot_question_backslash() {
+    let mut lexer = Lexer::new(r"rate.annual, is_valid?, \path\name");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::NamedRange(n) if n == "rate.annual"));
+    assert!(matches!(&tokens[2].value, Token::NamedRange(n) if n == "is_valid?"));
+    assert!(matches!(&tokens[4].value, Token::NamedRange(n) if n == r"\path\name"));
+}
+
+#[test]
+fn test_named_range_with_extended_characters() {
+    let mut lexer = Lexer::new("Größe, €Rate, 税率");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::NamedRange(n) if n == "Größe"));
+    assert!(matches!(&tokens[2].value, Token::NamedRange(n) if n == "€Rate"));
+    assert!(matches!(&tokens[4].value, Token::NamedRange(n) if n == "税率"));
+}
+
+#[test]
+fn test_named_range_range_operator() {
+    let mut lexer = Lexer::new("Start:Finish");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::NamedRange(n) if n == "Start"));
+    assert!(matches!(tokens[1].value, Token::Colon));
+    assert!(matches!(&tokens[2].value, Token::NamedRange(n) if n == "Finish"));
+}
+
+// ============================================================================
+// SPEC: NR-COMBINATION - names starting like another token
+// ============================================================================
+
+#[test]
+fn test_named_range_starting_with_boolean() {
+    let mut lexer = Lexer::new("TRUE_VALUE, FALSEHOOD, true.x");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::NamedRange(n) if n == "TRUE_VALUE"));
+    assert!(matches!(&tokens[2].value, Token::NamedRange(n) if n == "FALSEHOOD"));
+    assert!(matches!(&tokens[4].value, Token::NamedRange(n) if n == "true.x"));
+}
+
+#[test]
+fn test_named_range_starting_with_cell() {
+    let mut lexer = Lexer::new("A1B, B2_total, C3.rate");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::NamedRange(n) if n == "A1B"));
+    assert!(matches!(&tokens[2].value, Token::NamedRange(n) if n == "B2_total"));
+    assert!(matches!(&tokens[4].value, Token::NamedRange(n) if n == "C3.rate"));
+}
+
+#[test]
+fn test_named_range_beyond_sheet_limits() {
+    let mut lexer = Lexer::new("XFE1, ABCD12, A1048577");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::NamedRange(n) if n == "XFE1"));
+    assert!(matches!(&tokens[2].value, Token::NamedRange(n) if n == "ABCD12"));
+    assert!(matches!(&tokens[4].value, Token::NamedRange(n) if n == "A1048577"));
+}
+
+#[test]
+fn test_absolute_reference_beyond_limits_is_error() {
+    let mut lexer = Lexer::new("$ABCD12");
+    let result = lexer.tokenize();
+    assert!(matches!(result, Err(LexerError::InvalidCellReference(s, _)) if s == "$ABCD12"));
+}
+
+#[test]
+fn test_cell_still_wins_over_name() {
+    let mut lexer = Lexer::new("AB12");
+    let tokens = lexer.tokenize().unwrap();
+    assert_eq!(tokens[0].value, Token::Cell(CellRef::new(28, 12)));
+}
+
+// ============================================================================
+// SPEC: RESERVED-NAME - _xlnm\. [A-Z_]+
+// ============================================================================
+
+#[test]
+fn test_reserved_name() {
+    let mut lexer = Lexer::new("_xlnm.Print_Area");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::ReservedName(n) if n == "Print_Area"));
+}
+
+#[test]
+fn test_reserved_name_case_insensitive() {
+    let mut lexer = Lexer::new("_XLNM._FilterDatabase");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::ReservedName(n) if n == "_FilterDatabase"));
+}
+
+#[test]
+fn test_reserved_prefix_alone_is_named_range() {
+    let mut lexer = Lexer::new("_xlnm.");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::NamedRange(n) if n == "_xlnm."));
+}
@@ -1,65 +1,122 @@
 use expy::bindings::lexer::Lexer;
-use expy::bindings::token::Token;
+use expy::bindings::token::{LexerError, Token};
 
 // ============================================================================
-// SPEC: NUMBER - [0-9]+ .? [0-9]* (e [0-9]+)?
+// SPEC: NUMBER - [0-9]+ .? [0-9]* (e [+-]? [0-9]+)?
 // ============================================================================
 
 #[test]
 fn test_number_integer() {
     let mut lexer = Lexer::new("0 1 123 999999");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Number(n) if n == 0.0));
-    assert!(matches!(tokens[1], Token::Number(n) if n == 1.0));
-    assert!(matches!(tokens[2], Token::Number(n) if n == 123.0));
-    assert!(matches!(tokens[3], Token::Number(n) if n == 999999.0));
+    assert!(matches!(tokens[0].value, Token::Number(n) if n == 0.0));
+    assert!(matches!(tokens[1].value, Token::Number(n) if n == 1.0));
+    assert!(matches!(tokens[2].value, Token::Number(n) if n == 123.0));
+    assert!(matches!(tokens[3].value, Token::Number(n) if n == 999999.0));
 }
 
 #[test]
 fn test_number_with_trailing_dot() {
     let mut lexer = Lexer::new("123.");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Number(n) if n == 123.0));
+    assert!(matches!(tokens[0].value, Token::Number(n) if n == 123.0));
 }
 
 #[test]
 fn test_number_with_decimal() {
     let mut lexer = Lexer::new("123.456 0.5 999.001");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Number(n) if (n - 123.456).abs() < 0.0001));
-    assert!(matches!(tokens[1], Token::Number(n) if n == 0.5));
-    assert!(matches!(tokens[2], Token::Number(n) if (n - 999.001).abs() < 0.0001));
+    assert!(matches!(tokens[0].value, Token::Number(n) if (n - 123.456).abs() < 0.0001));
+    assert!(matches!(tokens[1].value, Token::Number(n) if n == 0.5));
+    assert!(matches!(tokens[2].value, Token::Number(n) if (n - 999.001).abs() < 0.0001));
 }
 
 #[test]
 fn test_number_scientific_notation() {
     let mut lexer = Lexer::new("1e5 2e10 999e3");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Number(n) if n == 1e5));
-    assert!(matches!(tokens[1], Token::Number(n) if n == 2e10));
-    assert!(matches!(tokens[2], Token::Number(n) if n == 999e3));
+    assert!(matches!(tokens[0].value, Token::Number(n) if n == 1e5));
+    assert!(matches!(tokens[1].value, Token::Number(n) if n == 2e10));
+    assert!(matches!(tokens[2].value, Token::Number(n) if n == 999e3));
 }
 
 #[test]
 fn test_number_scientific_with_decimal() {
     let mut lexer = Lexer::new("1.5e5 2.75e10 0.5e3");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Number(n) if n == 1.5e5));
-    assert!(matches!(tokens[1], Token::Number(n) if n == 2.75e10));
-    assert!(matches!(tokens[2], Token::Number(n) if n == 0.5e3));
+    assert!(matches!(tokens[0].value, Token::Number(n) if n == 1.5e5));
+    assert!(matches!(tokens[1].value, Token::Number(n) if n == 2.75e10));
+    assert!(matches!(tokens[2].value, Token::Number(n) if n == 0.5e3));
 }
 
 #[test]
 fn test_number_scientific_uppercase_e() {
     let mut lexer = Lexer::new("1E5 2.5E10");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Number(n) if n == 1e5));
-    assert!(matches!(tokens[1], Token::Number(n) if n == 2.5e10));
+    assert!(matches!(tokens[0].value, Token::Number(n) if n == 1e5));
+    assert!(matches!(tokens[1].value, Token::Number(n) if n == 2.5e10));
 }
 
 #[test]
 fn test_number_trailing_dot_with_scientific() {
     let mut lexer = Lexer::new("123.e5");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Number(n) if n == 123e5));
+    assert!(matches!(tokens[0].value, Token::Number(n) if n == 123e5));
+}
+
+#[test]
+fn test_number_signed_exponent() {
+    let mut lexer = Lexer::new("1E+5 2.5e-3 7e+0");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[0].value, Token::Number(n) if n == 1e5));
+    assert!(matches!(tokens[1].value, Token::Number(n) if n == 2.5e-3));
+    assert!(matches!(tokens[2].value, Token::Number(n) if n == 7.0));
+    assert!(matches!(tokens[3].value, Token::Eof));
+}
+
+#[test]
+fn test_number_signed_exponent_in_expression() {
+    let mut lexer = Lexer::new("1e-2-1E+2");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[0].value, Token::Number(n) if n == 1e-2));
+    assert!(matches!(tokens[1].value, Token::Minus));
+    assert!(matches!(tokens[2].value, Token::Number(n) if n == 1e2));
+}
+
+#[test]
+fn test_number_leading_dot() {
+    let mut lexer = Lexer::new(".5 .25e2 -.75");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[0].value, Token::Number(n) if n == 0.5));
+    assert!(matches!(tokens[1].value, Token::Number(n) if n == 25.0));
+    assert!(matches!(tokens[2].value, Token::Minus));
+    assert!(matches!(tokens[3].value, Token::Number(n) if n == 0.75));
+}
+
+#[test]
+fn test_lone_dot_is_unexpected() {
+    let mut lexer = Lexer::new(". 5");
+    let result = lexer.tokenize();
+    assert!(matches!(result, Err(LexerError::UnexpectedChar('.', _))));
+}
+
+#[test]
+fn test_number_exponent_missing_digits() {
+    let mut lexer = Lexer::new("1e");
+    let result = lexer.tokenize();
+    assert!(matches!(result, Err(LexerError::MissingExponentDigits(s, _)) if s == "1e"));
+}
+
+#[test]
+fn test_number_signed_exponent_missing_digits() {
+    let mut lexer = Lexer::new("2.5E+ 1");
+    let result = lexer.tokenize();
+    assert!(matches!(result, Err(LexerError::MissingExponentDigits(s, _)) if s == "2.5E+"));
+}
+
+#[test]
+fn test_number_out_of_range() {
+    let mut lexer = Lexer::new("1e999");
+    let result = lexer.tokenize();
+    assert!(matches!(result, Err(LexerError::InvalidNumber(s, _)) if s == "1e999"));
 }
@@ -1,5 +1,5 @@
 use expy::bindings::lexer::Lexer;
-use expy::bindings::token::Token;
+use expy::bindings::token::{CellRef, Token};
 
 // ============================================================================
 // SPEC: hUnOpPrefixi - '+' | '-'
@@ -9,38 +9,38 @@ use expy::bindings::token::Token;
 fn test_unary_plus() {
     let mut lexer = Lexer::new("+");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Plus));
+    assert!(matches!(tokens[0].value, Token::Plus));
 }
 
 #[test]
 fn test_unary_plus_with_number() {
     let mut lexer = Lexer::new("+4.0");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Plus));
-    assert!(matches!(tokens[1], Token::Number(4.0)));
+    assert!(matches!(tokens[0].value, Token::Plus));
+    assert!(matches!(tokens[1].value, Token::Number(4.0)));
 }
 
 #[test]
 fn test_unary_minus() {
     let mut lexer = Lexer::new("-");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Minus));
+    assert!(matches!(tokens[0].value, Token::Minus));
 }
 
 #[test]
 fn test_unary_minus_with_number() {
     let mut lexer = Lexer::new("-4.0");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Minus));
-    assert!(matches!(tokens[1], Token::Number(4.0)));
+    assert!(matches!(tokens[0].value, Token::Minus));
+    assert!(matches!(tokens[1].value, Token::Number(4.0)));
 }
 
 #[test]
 fn test_unary_operators_both() {
     let mut lexer = Lexer::new("+ -");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Plus));
-    assert!(matches!(tokens[1], Token::Minus));
+    assert!(matches!(tokens[0].value, Token::Plus));
+    assert!(matches!(tokens[1].value, Token::Minus));
 }
 
 // ============================================================================
@@ -53,38 +53,38 @@ fn test_unary_operators_both() {
 fn test_binary_multiply() {
     let mut lexer = Lexer::new("*");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Multiply));
+    assert!(matches!(tokens[0].value, Token::Multiply));
 }
 
 #[test]
 fn test_binary_divide() {
     let mut lexer = Lexer::new("/");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Divide));
+    assert!(matches!(tokens[0].value, Token::Divide));
 }
 
 #[test]
 fn test_binary_power() {
     let mut lexer = Lexer::new("^");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Power));
+    assert!(matches!(tokens[0].value, Token::Power));
 }
 
 #[test]
 fn test_binary_arithmetic_expression() {
     let mut lexer = Lexer::new("1 + 2 * 3 / 4 - 5 ^ 6");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Number(n) if n == 1.0));
-    assert!(matches!(tokens[1], Token::Plus));
-    assert!(matches!(tokens[2], Token::Number(n) if n == 2.0));
-    assert!(matches!(tokens[3], Token::Multiply));
-    assert!(matches!(tokens[4], Token::Number(n) if n == 3.0));
-    assert!(matches!(tokens[5], Token::Divide));
-    assert!(matches!(tokens[6], Token::Number(n) if n == 4.0));
-    assert!(matches!(tokens[7], Token::Minus));
-    assert!(matches!(tokens[8], Token::Number(n) if n == 5.0));
-    assert!(matches!(tokens[9], Token::Power));
-    assert!(matches!(tokens[10], Token::Number(n) if n == 6.0));
+    assert!(matches!(tokens[0].value, Token::Number(n) if n == 1.0));
+    assert!(matches!(tokens[1].value, Token::Plus));
+    assert!(matches!(tokens[2].value, Token::Number(n) if n == 2.0));
+    assert!(matches!(tokens[3].value, Token::Multiply));
+    assert!(matches!(tokens[4].value, Token::Number(n) if n == 3.0));
+    assert!(matches!(tokens[5].value, Token::Divide));
+    assert!(matches!(tokens[6].value, Token::Number(n) if n == 4.0));
+    assert!(matches!(tokens[7].value, Token::Minus));
+    assert!(matches!(tokens[8].value, Token::Number(n) if n == 5.0));
+    assert!(matches!(tokens[9].value, Token::Power));
+    assert!(matches!(tokens[10].value, Token::Number(n) if n == 6.0));
 }
 
 // String Concatenation Operator
@@ -92,16 +92,16 @@ fn test_binary_arithmetic_expression() {
 fn test_binary_concatenate() {
     let mut lexer = Lexer::new("&");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Concatenate));
+    assert!(matches!(tokens[0].value, Token::Concatenate));
 }
 
 #[test]
 fn test_binary_concatenate_strings() {
     let mut lexer = Lexer::new(r#""hello" & "world""#);
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(&tokens[0], Token::String(s) if s == "hello"));
-    assert!(matches!(tokens[1], Token::Concatenate));
-    assert!(matches!(&tokens[2], Token::String(s) if s == "world"));
+    assert!(matches!(&tokens[0].value, Token::String(s) if s == "hello"));
+    assert!(matches!(tokens[1].value, Token::Concatenate));
+    assert!(matches!(&tokens[2].value, Token::String(s) if s == "world"));
 }
 
 // Comparison Operators - Single Character
@@ -109,21 +109,21 @@ fn test_binary_concatenate_strings() {
 fn test_binary_equal() {
     let mut lexer = Lexer::new("=");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Equal));
+    assert!(matches!(tokens[0].value, Token::Equal));
 }
 
 #[test]
 fn test_binary_less() {
     let mut lexer = Lexer::new("<");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Less));
+    assert!(matches!(tokens[0].value, Token::Less));
 }
 
 #[test]
 fn test_binary_greater() {
     let mut lexer = Lexer::new(">");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Greater));
+    assert!(matches!(tokens[0].value, Token::Greater));
 }
 
 // Comparison Operators - Two Character
@@ -131,40 +131,40 @@ fn test_binary_greater() {
 fn test_binary_less_equal() {
     let mut lexer = Lexer::new("<=");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::LessEqual));
+    assert!(matches!(tokens[0].value, Token::LessEqual));
 }
 
 #[test]
 fn test_binary_greater_equal() {
     let mut lexer = Lexer::new(">=");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::GreaterEqual));
+    assert!(matches!(tokens[0].value, Token::GreaterEqual));
 }
 
 #[test]
 fn test_binary_not_equal() {
     let mut lexer = Lexer::new("<>");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::NotEqual));
+    assert!(matches!(tokens[0].value, Token::NotEqual));
 }
 
 #[test]
 fn test_binary_comparison_expression() {
     let mut lexer = Lexer::new("1 < 2 <= 3 > 4 >= 5 = 6 <> 7");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Number(n) if n == 1.0));
-    assert!(matches!(tokens[1], Token::Less));
-    assert!(matches!(tokens[2], Token::Number(n) if n == 2.0));
-    assert!(matches!(tokens[3], Token::LessEqual));
-    assert!(matches!(tokens[4], Token::Number(n) if n == 3.0));
-    assert!(matches!(tokens[5], Token::Greater));
-    assert!(matches!(tokens[6], Token::Number(n) if n == 4.0));
-    assert!(matches!(tokens[7], Token::GreaterEqual));
-    assert!(matches!(tokens[8], Token::Number(n) if n == 5.0));
-    assert!(matches!(tokens[9], Token::Equal));
-    assert!(matches!(tokens[10], Token::Number(n) if n == 6.0));
-    assert!(matches!(tokens[11], Token::NotEqual));
-    assert!(matches!(tokens[12], Token::Number(n) if n == 7.0));
+    assert!(matches!(tokens[0].value, Token::Number(n) if n == 1.0));
+    assert!(matches!(tokens[1].value, Token::Less));
+    assert!(matches!(tokens[2].value, Token::Number(n) if n == 2.0));
+    assert!(matches!(tokens[3].value, Token::LessEqual));
+    assert!(matches!(tokens[4].value, Token::Number(n) if n == 3.0));
+    assert!(matches!(tokens[5].value, Token::Greater));
+    assert!(matches!(tokens[6].value, Token::Number(n) if n == 4.0));
+    assert!(matches!(tokens[7].value, Token::GreaterEqual));
+    assert!(matches!(tokens[8].value, Token::Number(n) if n == 5.0));
+    assert!(matches!(tokens[9].value, Token::Equal));
+    assert!(matches!(tokens[10].value, Token::Number(n) if n == 6.0));
+    assert!(matches!(tokens[11].value, Token::NotEqual));
+    assert!(matches!(tokens[12].value, Token::Number(n) if n == 7.0));
 }
 
 // Edge Cases - Operators without spaces
@@ -172,33 +172,126 @@ fn test_binary_comparison_expression() {
 fn test_binary_operators_no_spaces() {
     let mut lexer = Lexer::new("1+2*3/4-5^6");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Number(n) if n == 1.0));
-    assert!(matches!(tokens[1], Token::Plus));
-    assert!(matches!(tokens[2], Token::Number(n) if n == 2.0));
-    assert!(matches!(tokens[3], Token::Multiply));
-    assert!(matches!(tokens[4], Token::Number(n) if n == 3.0));
-    assert!(matches!(tokens[5], Token::Divide));
-    assert!(matches!(tokens[6], Token::Number(n) if n == 4.0));
-    assert!(matches!(tokens[7], Token::Minus));
-    assert!(matches!(tokens[8], Token::Number(n) if n == 5.0));
-    assert!(matches!(tokens[9], Token::Power));
-    assert!(matches!(tokens[10], Token::Number(n) if n == 6.0));
+    assert!(matches!(tokens[0].value, Token::Number(n) if n == 1.0));
+    assert!(matches!(tokens[1].value, Token::Plus));
+    assert!(matches!(tokens[2].value, Token::Number(n) if n == 2.0));
+    assert!(matches!(tokens[3].value, Token::Multiply));
+    assert!(matches!(tokens[4].value, Token::Number(n) if n == 3.0));
+    assert!(matches!(tokens[5].value, Token::Divide));
+    assert!(matches!(tokens[6].value, Token::Number(n) if n == 4.0));
+    assert!(matches!(tokens[7].value, Token::Minus));
+    assert!(matches!(tokens[8].value, Token::Number(n) if n == 5.0));
+    assert!(matches!(tokens[9].value, Token::Power));
+    assert!(matches!(tokens[10].value, Token::Number(n) if n == 6.0));
 }
 
 #[test]
 fn test_binary_all_operators() {
     let mut lexer = Lexer::new("+ - * / ^ & = < > <= >= <>");
     let tokens = lexer.tokenize().unwrap();
-    assert!(matches!(tokens[0], Token::Plus));
-    assert!(matches!(tokens[1], Token::Minus));
-    assert!(matches!(tokens[2], Token::Multiply));
-    assert!(matches!(tokens[3], Token::Divide));
-    assert!(matches!(tokens[4], Token::Power));
-    assert!(matches!(tokens[5], Token::Concatenate));
-    assert!(matches!(tokens[6], Token::Equal));
-    assert!(matches!(tokens[7], Token::Less));
-    assert!(matches!(tokens[8], Token::Greater));
-    assert!(matches!(tokens[9], Token::LessEqual));
-    assert!(matches!(tokens[10], Token::GreaterEqual));
-    assert!(matches!(tokens[11], Token::NotEqual));
+    assert!(matches!(tokens[0].value, Token::Plus));
+    assert!(matches!(tokens[1].value, Token::Minus));
+    assert!(matches!(tokens[2].value, Token::Multiply));
+    assert!(matches!(tokens[3].value, Token::Divide));
+    assert!(matches!(tokens[4].value, Token::Power));
+    assert!(matches!(tokens[5].value, Token::Concatenate));
+    assert!(matches!(tokens[6].value, Token::Equal));
+    assert!(matches!(tokens[7].value, Token::Less));
+    assert!(matches!(tokens[8].value, Token::Greater));
+    assert!(matches!(tokens[9].value, Token::LessEqual));
+    assert!(matches!(tokens[10].value, Token::GreaterEqual));
+    assert!(matches!(tokens[11].value, Token::NotEqual));
+}
+
+// ============================================================================
+// SPEC: hUnOpPostfixi - '%'
+// ============================================================================
+
+#[test]
+fn test_postfix_percent() {
+    let mut lexer = Lexer::new("50%");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[0].value, Token::Number(n) if n == 50.0));
+    assert!(matches!(tokens[1].value, Token::Percent));
+    assert!(matches!(tokens[2].value, Token::Eof));
+}
+
+#[test]
+fn test_postfix_percent_on_reference() {
+    let mut lexer = Lexer::new("A1% * 2");
+    let tokens = lexer.tokenize().unwrap();
+    assert_eq!(tokens[0].value, Token::Cell(CellRef::new(1, 1)));
+    assert!(matches!(tokens[1].value, Token::Percent));
+    assert!(matches!(tokens[2].value, Token::Multiply));
+    assert!(matches!(tokens[3].value, Token::Number(n) if n == 2.0));
+}
+
+// ============================================================================
+// SPEC: hBinOpi - ' ' (intersection)
+// ============================================================================
+
+#[test]
+fn test_intersect_ranges() {
+    let mut lexer = Lexer::new("=A1:C3 B2:D4");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[0].value, Token::Equal));
+    assert_eq!(tokens[1].value, Token::Cell(CellRef::new(1, 1)));
+    assert!(matches!(tokens[2].value, Token::Colon));
+    assert_eq!(tokens[3].value, Token::Cell(CellRef::new(3, 3)));
+    assert!(matches!(tokens[4].value, Token::Intersect));
+    assert_eq!(tokens[5].value, Token::Cell(CellRef::new(2, 2)));
+    assert!(matches!(tokens[6].value, Token::Colon));
+    assert_eq!(tokens[7].value, Token::Cell(CellRef::new(4, 4)));
+    assert!(matches!(tokens[8].value, Token::Eof));
+}
+
+#[test]
+fn test_intersect_multiple_spaces_is_single_operator() {
+    let mut lexer = Lexer::new("Prices \t Jan_Sales");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::NamedRange(n) if n == "Prices"));
+    assert!(matches!(tokens[1].value, Token::Intersect));
+    assert!(matches!(&tokens[2].value, Token::NamedRange(n) if n == "Jan_Sales"));
+    assert!(matches!(tokens[3].value, Token::Eof));
+}
+
+#[test]
+fn test_intersect_with_sheet_and_parentheses() {
+    let mut lexer = Lexer::new("(A:A) Sheet2!1:1");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[0].value, Token::LeftParen));
+    assert!(matches!(tokens[1].value, Token::VerticalRange(..)));
+    assert!(matches!(tokens[2].value, Token::RightParen));
+    assert!(matches!(tokens[3].value, Token::Intersect));
+    assert!(matches!(&tokens[4].value, Token::Sheet(s) if s == "Sheet2"));
+    assert!(matches!(tokens[5].value, Token::Exclamation));
+    assert!(matches!(tokens[6].value, Token::HorizontalRange(..)));
+}
+
+#[test]
+fn test_whitespace_around_operators_is_not_intersect() {
+    let mut lexer = Lexer::new("A1 + B1 ");
+    let tokens = lexer.tokenize().unwrap();
+    assert_eq!(tokens[0].value, Token::Cell(CellRef::new(1, 1)));
+    assert!(matches!(tokens[1].value, Token::Plus));
+    assert_eq!(tokens[2].value, Token::Cell(CellRef::new(2, 1)));
+    assert!(matches!(tokens[3].value, Token::Eof));
+}
+
+#[test]
+fn test_whitespace_after_comma_is_not_intersect() {
+    let mut lexer = Lexer::new("SUM(A1, B1)");
+    let tokens = lexer.tokenize().unwrap();
+    assert_eq!(tokens[1].value, Token::Cell(CellRef::new(1, 1)));
+    assert!(matches!(tokens[2].value, Token::Comma));
+    assert_eq!(tokens[3].value, Token::Cell(CellRef::new(2, 1)));
+    assert!(matches!(tokens[4].value, Token::RightParen));
+}
+
+#[test]
+fn test_whitespace_between_literals_is_not_intersect() {
+    let mut lexer = Lexer::new("A1 1");
+    let tokens = lexer.tokenize().unwrap();
+    assert_eq!(tokens[0].value, Token::Cell(CellRef::new(1, 1)));
+    assert!(matches!(tokens[1].value, Token::Number(n) if n == 1.0));
 }
@@ -0,0 +1,134 @@
+use expy::bindings::lexer::Lexer;
+use expy::bindings::token::{CellRef, LexerError, RangeBound, Token};
+
+fn bound(index: u32, absolute: bool) -> RangeBound {
+    RangeBound { index, absolute }
+}
+
+// ============================================================================
+// SPEC: ':' - range operator
+// ============================================================================
+
+#[test]
+fn test_colon() {
+    let mut lexer = Lexer::new(":");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[0].value, Token::Colon));
+}
+
+#[test]
+fn test_cell_range() {
+    let mut lexer = Lexer::new("A1:B10");
+    let tokens = lexer.tokenize().unwrap();
+    assert_eq!(tokens[0].value, Token::Cell(CellRef::new(1, 1)));
+    assert!(matches!(tokens[1].value, Token::Colon));
+    assert_eq!(tokens[2].value, Token::Cell(CellRef::new(2, 10)));
+    assert!(matches!(tokens[3].value, Token::Eof));
+}
+
+#[test]
+fn test_number_followed_by_colon() {
+    let mut lexer = Lexer::new("1:A1");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[0].value, Token::Number(n) if n == 1.0));
+    assert!(matches!(tokens[1].value, Token::Colon));
+    assert_eq!(tokens[2].value, Token::Cell(CellRef::new(1, 1)));
+}
+
+#[test]
+fn test_decimal_is_not_row_range() {
+    let mut lexer = Lexer::new("1.5:2");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[0].value, Token::Number(n) if n == 1.5));
+    assert!(matches!(tokens[1].value, Token::Colon));
+    assert!(matches!(tokens[2].value, Token::Number(n) if n == 2.0));
+}
+
+// ============================================================================
+// SPEC: HORIZONTAL-RANGE - $? [0-9]+ : $? [0-9]+
+// ============================================================================
+
+#[test]
+fn test_horizontal_range_simple() {
+    let mut lexer = Lexer::new("1:5");
+    let tokens = lexer.tokenize().unwrap();
+    assert_eq!(
+        tokens[0].value,
+        Token::HorizontalRange(bound(1, false), bound(5, false))
+    );
+    assert!(matches!(tokens[1].value, Token::Eof));
+}
+
+#[test]
+fn test_horizontal_range_absolute() {
+    let mut lexer = Lexer::new("$3:$7, 3:$7");
+    let tokens = lexer.tokenize().unwrap();
+    assert_eq!(
+        tokens[0].value,
+        Token::HorizontalRange(bound(3, true), bound(7, true))
+    );
+    assert_eq!(
+        tokens[2].value,
+        Token::HorizontalRange(bound(3, false), bound(7, true))
+    );
+}
+
+#[test]
+fn test_horizontal_range_out_of_bounds() {
+    let mut lexer = Lexer::new("1:1048577");
+    let result = lexer.tokenize();
+    assert!(matches!(result, Err(LexerError::InvalidRange(s, _)) if s == "1:1048577"));
+}
+
+#[test]
+fn test_horizontal_range_row_zero() {
+    let mut lexer = Lexer::new("0:3");
+    let result = lexer.tokenize();
+    assert!(matches!(result, Err(LexerError::InvalidRange(s, _)) if s == "0:3"));
+}
+
+// ============================================================================
+// SPEC: VERTICAL-RANGE - $? [A-Z]+ : $? [A-Z]+
+// ============================================================================
+
+#[test]
+fn test_vertical_range_simple() {
+    let mut lexer = Lexer::new("A:C");
+    let tokens = lexer.tokenize().unwrap();
+    assert_eq!(
+        tokens[0].value,
+        Token::VerticalRange(bound(1, false), bound(3, false))
+    );
+    assert!(matches!(tokens[1].value, Token::Eof));
+}
+
+#[test]
+fn test_vertical_range_single_column() {
+    let mut lexer = Lexer::new("a:a");
+    let tokens = lexer.tokenize().unwrap();
+    assert_eq!(
+        tokens[0].value,
+        Token::VerticalRange(bound(1, false), bound(1, false))
+    );
+}
+
+#[test]
+fn test_vertical_range_absolute() {
+    let mut lexer = Lexer::new("$A:$Z, AA:$XFD");
+    let tokens = lexer.tokenize().unwrap();
+    assert_eq!(
+        tokens[0].value,
+        Token::VerticalRange(bound(1, true), bound(26, true))
+    );
+    assert_eq!(
+        tokens[2].value,
+        Token::VerticalRange(bound(27, false), bound(16_384, true))
+    );
+}
+
+#[test]
+fn test_vertical_range_out_of_bounds() {
+    let mut lexer = Lexer::new("A:$XFE");
+    let result = lexer.tokenize();
+    assert!(matches!(result, Err(LexerError::InvalidRange(s, _)) if s == "A:$XFE"));
+}
@@ -0,0 +1,162 @@
+use expy::bindings::lexer::Lexer;
+use expy::bindings::token::{CellRef, LexerError, Token};
+
+// ============================================================================
+// SPEC: SHEET - ★2+ !
+// ============================================================================
+
+#[test]
+fn test_sheet_simple() {
+    let mut lexer = Lexer::new("Sheet2!A1");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::Sheet(s) if s == "Sheet2"));
+    assert!(matches!(tokens[1].value, Token::Exclamation));
+    assert_eq!(tokens[2].value, Token::Cell(CellRef::new(1, 1)));
+    assert!(matches!(tokens[3].value, Token::Eof));
+}
+
+#[test]
+fn test_sheet_with_extended_characters() {
+    let mut lexer = Lexer::new("Données_2024.v2!$B$3");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::Sheet(s) if s == "Données_2024.v2"));
+    assert!(matches!(tokens[1].value, Token::Exclamation));
+    assert!(matches!(
+        tokens[2].value,
+        Token::Cell(CellRef {
+            column: 2,
+            row: 3,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_sheet_named_like_cell() {
+    let mut lexer = Lexer::new("A1!B2");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::Sheet(s) if s == "A1"));
+    assert!(matches!(tokens[1].value, Token::Exclamation));
+    assert_eq!(tokens[2].value, Token::Cell(CellRef::new(2, 2)));
+}
+
+#[test]
+fn test_sheet_in_expression() {
+    let mut lexer = Lexer::new("=A1*Sheet2!B3");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[0].value, Token::Equal));
+    assert_eq!(tokens[1].value, Token::Cell(CellRef::new(1, 1)));
+    assert!(matches!(tokens[2].value, Token::Multiply));
+    assert!(matches!(&tokens[3].value, Token::Sheet(s) if s == "Sheet2"));
+    assert!(matches!(tokens[4].value, Token::Exclamation));
+    assert_eq!(tokens[5].value, Token::Cell(CellRef::new(2, 3)));
+}
+
+#[test]
+fn test_sheet_with_range() {
+    let mut lexer = Lexer::new("Data!A:A");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::Sheet(s) if s == "Data"));
+    assert!(matches!(tokens[1].value, Token::Exclamation));
+    assert!(matches!(tokens[2].value, Token::VerticalRange(..)));
+}
+
+#[test]
+fn test_exclamation() {
+    let mut lexer = Lexer::new("!");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[0].value, Token::Exclamation));
+}
+
+// ============================================================================
+// SPEC: SHEET-QUOTED - ' (★3 | '')* ' !
+// ============================================================================
+
+#[test]
+fn test_sheet_quoted() {
+    let mut lexer = Lexer::new("'Q1 Budget'!B4");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::SheetQuoted(s) if s == "Q1 Budget"));
+    assert!(matches!(tokens[1].value, Token::Exclamation));
+    assert_eq!(tokens[2].value, Token::Cell(CellRef::new(2, 4)));
+}
+
+#[test]
+fn test_sheet_quoted_special_characters() {
+    let mut lexer = Lexer::new("'Sales (EU) & Co. #1'!A1");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::SheetQuoted(s) if s == "Sales (EU) & Co. #1"));
+}
+
+#[test]
+fn test_sheet_quoted_escaped_quote() {
+    let mut lexer = Lexer::new("'Bob''s Sheet'!A1");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::SheetQuoted(s) if s == "Bob's Sheet"));
+    assert!(matches!(tokens[1].value, Token::Exclamation));
+}
+
+#[test]
+fn test_sheet_quoted_unterminated() {
+    let mut lexer = Lexer::new("'Q1 Budget!A1");
+    let result = lexer.tokenize();
+    assert!(matches!(result, Err(LexerError::UnterminatedSheetName(_))));
+}
+
+#[test]
+fn test_sheet_quoted_invalid_character() {
+    let mut lexer = Lexer::new("'Q1/Q2'!A1");
+    let result = lexer.tokenize();
+    assert!(matches!(result, Err(LexerError::UnexpectedChar('/', _))));
+}
+
+#[test]
+fn test_sheet_quoted_empty() {
+    let mut lexer = Lexer::new("''!A1");
+    let result = lexer.tokenize();
+    assert!(matches!(result, Err(LexerError::UnexpectedChar('\'', _))));
+}
+
+// ============================================================================
+// SPEC: MULTIPLE-SHEETS - ★2+ : ★2+ !
+// ============================================================================
+
+#[test]
+fn test_multiple_sheets() {
+    let mut lexer = Lexer::new("Jan:Dec!C3");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[0].value, Token::MultipleSheets(a, b) if a == "Jan" && b == "Dec"));
+    assert!(matches!(tokens[1].value, Token::Exclamation));
+    assert_eq!(tokens[2].value, Token::Cell(CellRef::new(3, 3)));
+}
+
+#[test]
+fn test_multiple_sheets_not_vertical_range() {
+    let mut lexer = Lexer::new("A:C, A:C!A1");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[0].value, Token::VerticalRange(..)));
+    assert!(matches!(&tokens[2].value, Token::MultipleSheets(a, b) if a == "A" && b == "C"));
+}
+
+// ============================================================================
+// SPEC: MULTIPLE-SHEETS-QUOTED - ' ★3+ : ★3+ ' !
+// ============================================================================
+
+#[test]
+fn test_multiple_sheets_quoted() {
+    let mut lexer = Lexer::new("'Jan 2020:Dec 2020'!C3");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(
+        &tokens[0].value,
+        Token::MultipleSheetsQuoted(a, b) if a == "Jan 2020" && b == "Dec 2020"
+    ));
+    assert!(matches!(tokens[1].value, Token::Exclamation));
+    assert_eq!(tokens[2].value, Token::Cell(CellRef::new(3, 3)));
+}
+
+#[test]
+fn test_multiple_sheets_quoted_missing_name() {
+    let mut lexer = Lexer::new("'Jan:'!C3");
+    let result = lexer.tokenize();
+    assert!(matches!(result, Err(LexerError::UnexpectedChar('\'', _))));
+}
@@ -0,0 +1,113 @@
+use expy::bindings::lexer::Lexer;
+use expy::bindings::token::{LexerError, Span, Token};
+
+fn span(start: usize, end: usize, line: usize, column: usize) -> Span {
+    Span {
+        start,
+        end,
+        char_start: start,
+        char_end: end,
+        line,
+        column,
+    }
+}
+
+// ============================================================================
+// Token spans
+// ============================================================================
+
+#[test]
+fn test_span_simple_tokens() {
+    let mut lexer = Lexer::new("=A1+$B$2");
+    let tokens = lexer.tokenize().unwrap();
+    assert_eq!(tokens[0].span, span(0, 1, 1, 1));
+    assert_eq!(tokens[1].span, span(1, 3, 1, 2));
+    assert_eq!(tokens[2].span, span(3, 4, 1, 4));
+    assert_eq!(tokens[3].span, span(4, 8, 1, 5));
+    assert!(matches!(tokens[4].value, Token::Eof));
+    assert_eq!(tokens[4].span, span(8, 8, 1, 9));
+}
+
+#[test]
+fn test_span_excludes_whitespace() {
+    let mut lexer = Lexer::new("  SUM( 1 )");
+    let tokens = lexer.tokenize().unwrap();
+    assert_eq!(tokens[0].span, span(2, 6, 1, 3));
+    assert_eq!(tokens[1].span, span(7, 8, 1, 8));
+    assert_eq!(tokens[2].span, span(9, 10, 1, 10));
+}
+
+#[test]
+fn test_span_intersect_covers_whitespace() {
+    let mut lexer = Lexer::new("A1  B2");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[1].value, Token::Intersect));
+    assert_eq!(tokens[1].span, span(2, 4, 1, 3));
+    assert_eq!(tokens[2].span, span(4, 6, 1, 5));
+}
+
+#[test]
+fn test_span_byte_and_char_offsets() {
+    let mut lexer = Lexer::new(r#""é€" & Größe"#);
+    let tokens = lexer.tokenize().unwrap();
+    let string = tokens[0].span;
+    assert_eq!((string.start, string.end), (0, 7));
+    assert_eq!((string.char_start, string.char_end), (0, 4));
+
+    let name = tokens[2].span;
+    assert_eq!((name.start, name.end), (10, 17));
+    assert_eq!((name.char_start, name.char_end), (7, 12));
+    assert_eq!(name.column, 8);
+}
+
+#[test]
+fn test_span_multi_line() {
+    let mut lexer = Lexer::new("=IF(A1,\n  B1,\n  C1)");
+    let tokens = lexer.tokenize().unwrap();
+    let b1 = tokens[4].span;
+    assert_eq!((b1.line, b1.column), (2, 3));
+    let c1 = tokens[6].span;
+    assert_eq!((c1.line, c1.column), (3, 3));
+    assert_eq!(c1.char_start, 16);
+}
+
+#[test]
+fn test_span_file_in_quoted_sheet() {
+    let mut lexer = Lexer::new("'[1]Q1'!A1");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[0].value, Token::File(1)));
+    assert_eq!(tokens[0].span, span(0, 4, 1, 1));
+    assert!(matches!(tokens[1].value, Token::SheetQuoted(_)));
+    assert_eq!(tokens[1].span, span(4, 7, 1, 5));
+    assert_eq!(tokens[2].span, span(7, 8, 1, 8));
+}
+
+// ============================================================================
+// Error spans
+// ============================================================================
+
+#[test]
+fn test_error_span_unexpected_char() {
+    let mut lexer = Lexer::new("1 + @ - 2");
+    let result = lexer.tokenize();
+    assert_eq!(
+        result,
+        Err(LexerError::UnexpectedChar('@', span(4, 5, 1, 5)))
+    );
+}
+
+#[test]
+fn test_error_span_unterminated_string() {
+    let mut lexer = Lexer::new(r#"1 & "abc"#);
+    let err = lexer.tokenize().unwrap_err();
+    assert!(matches!(err, LexerError::UnterminatedString(_)));
+    assert_eq!(err.span(), span(4, 8, 1, 5));
+}
+
+#[test]
+fn test_error_span_invalid_cell() {
+    let mut lexer = Lexer::new("=1+\n$XFE$1");
+    let err = lexer.tokenize().unwrap_err();
+    assert!(matches!(err, LexerError::InvalidCellReference(ref s, _) if s == "$XFE$1"));
+    assert_eq!(err.span(), span(4, 10, 2, 1));
+}
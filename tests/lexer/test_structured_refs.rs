@@ -0,0 +1,184 @@
+use expy::bindings::lexer::Lexer;
+use expy::bindings::token::{LexerError, StructuredRef, TableItem, Token};
+
+fn structured(input: &str) -> StructuredRef {
+    let mut lexer = Lexer::new(input);
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(tokens[1].value, Token::Eof), "{tokens:?}");
+    match &tokens[0].value {
+        Token::StructuredRef(reference) => reference.clone(),
+        other => panic!("expected structured reference, got {other:?}"),
+    }
+}
+
+// ============================================================================
+// SPEC: SR-COLUMN - table name with bracketed column specifier
+// ============================================================================
+
+#[test]
+fn test_structured_ref_column() {
+    let reference = structured("Sales[Amount]");
+    assert_eq!(reference.table.as_deref(), Some("Sales"));
+    assert!(reference.items.is_empty());
+    assert_eq!(reference.column.as_deref(), Some("Amount"));
+    assert_eq!(reference.last_column, None);
+}
+
+#[test]
+fn test_structured_ref_in_sum() {
+    let mut lexer = Lexer::new("=SUM(Sales[Amount])");
+    let tokens = lexer.tokenize().unwrap();
+    assert!(matches!(&tokens[1].value, Token::ExcelFunction(f) if f == "SUM"));
+    assert!(
+        matches!(&tokens[2].value, Token::StructuredRef(r) if r.column.as_deref() == Some("Amount"))
+    );
+    assert!(matches!(tokens[3].value, Token::RightParen));
+}
+
+#[test]
+fn test_structured_ref_column_with_spaces() {
+    let reference = structured("Table1[Sales Amount]");
+    assert_eq!(reference.column.as_deref(), Some("Sales Amount"));
+}
+
+#[test]
+fn test_structured_ref_whole_table() {
+    let reference = structured("Table1[]");
+    assert_eq!(reference.table.as_deref(), Some("Table1"));
+    assert!(reference.items.is_empty());
+    assert_eq!(reference.column, None);
+}
+
+#[test]
+fn test_structured_ref_without_table() {
+    let reference = structured("[Amount]");
+    assert_eq!(reference.table, None);
+    assert_eq!(reference.column.as_deref(), Some("Amount"));
+}
+
+#[test]
+fn test_structured_ref_escaped_column() {
+    let reference = structured("Prices[Price'[USD']]");
+    assert_eq!(reference.column.as_deref(), Some("Price[USD]"));
+    let reference = structured("Prices[[It''s '#1]]");
+    assert_eq!(reference.column.as_deref(), Some("It's #1"));
+}
+
+// ============================================================================
+// This-row references: [@Col], [@[Col Name]]
+// ============================================================================
+
+#[test]
+fn test_structured_ref_this_row() {
+    let reference = structured("[@Qty]");
+    assert_eq!(reference.items, vec![TableItem::ThisRow]);
+    assert_eq!(reference.column.as_deref(), Some("Qty"));
+}
+
+#[test]
+fn test_structured_ref_this_row_bracketed() {
+    let reference = structured("Sales[@[Unit Price]]");
+    assert_eq!(reference.table.as_deref(), Some("Sales"));
+    assert_eq!(reference.items, vec![TableItem::ThisRow]);
+    assert_eq!(reference.column.as_deref(), Some("Unit Price"));
+}
+
+// ============================================================================
+// Special items: [#All], [#Data], [#Headers], [#Totals], [#This Row]
+// ============================================================================
+
+#[test]
+fn test_structured_ref_special_items() {
+    assert_eq!(structured("T[#All]").items, vec![TableItem::All]);
+    assert_eq!(structured("T[#Data]").items, vec![TableItem::Data]);
+    assert_eq!(structured("T[#headers]").items, vec![TableItem::Headers]);
+    assert_eq!(structured("T[#Totals]").items, vec![TableItem::Totals]);
+    assert_eq!(structured("T[#This Row]").items, vec![TableItem::ThisRow]);
+}
+
+#[test]
+fn test_structured_ref_item_and_column() {
+    let reference = structured("Sales[[#Totals],[Amount]]");
+    assert_eq!(reference.items, vec![TableItem::Totals]);
+    assert_eq!(reference.column.as_deref(), Some("Amount"));
+}
+
+#[test]
+fn test_structured_ref_multiple_items() {
+    let reference = structured("Sales[[#Headers], [#Data], [Amount]]");
+    assert_eq!(reference.items, vec![TableItem::Headers, TableItem::Data]);
+    assert_eq!(reference.column.as_deref(), Some("Amount"));
+}
+
+#[test]
+fn test_structured_ref_unknown_item() {
+    let mut lexer = Lexer::new("Sales[#Everything]");
+    let result = lexer.tokenize();
+    assert!(
+        matches!(result, Err(LexerError::InvalidStructuredReference(s, _)) if s == "#Everything")
+    );
+}
+
+// ============================================================================
+// Column ranges: [[Q1]:[Q4]]
+// ============================================================================
+
+#[test]
+fn test_structured_ref_column_range() {
+    let reference = structured("Budget[[Q1]:[Q4]]");
+    assert_eq!(reference.column.as_deref(), Some("Q1"));
+    assert_eq!(reference.last_column.as_deref(), Some("Q4"));
+}
+
+#[test]
+fn test_structured_ref_item_and_column_range() {
+    let reference = structured("Budget[[#Data],[Q1]:[Q4]]");
+    assert_eq!(reference.items, vec![TableItem::Data]);
+    assert_eq!(reference.column.as_deref(), Some("Q1"));
+    assert_eq!(reference.last_column.as_deref(), Some("Q4"));
+}
+
+// ============================================================================
+// Display and errors
+// ============================================================================
+
+#[test]
+fn test_structured_ref_display() {
+    for text in [
+        "Sales[Amount]",
+        "Sales[]",
+        "[@[Unit Price]]",
+        "Sales[#Totals]",
+        "Budget[[#Data],[Q1]:[Q4]]",
+        "Prices[Price'[USD']]",
+    ] {
+        assert_eq!(structured(text).to_string(), text);
+    }
+}
+
+#[test]
+fn test_structured_ref_unterminated() {
+    let mut lexer = Lexer::new("Sales[Amount");
+    let result = lexer.tokenize();
+    assert!(matches!(
+        result,
+        Err(LexerError::UnterminatedStructuredReference(_))
+    ));
+}
+
+#[test]
+fn test_structured_ref_unescaped_bracket() {
+    let mut lexer = Lexer::new("Sales[Amount[x]]");
+    let result = lexer.tokenize();
+    assert!(matches!(result, Err(LexerError::UnexpectedChar('[', _))));
+}
+
+#[test]
+fn test_structured_ref_empty_column() {
+    let mut lexer = Lexer::new("Sales[[ ]]");
+    let result = lexer.tokenize();
+    assert!(matches!(
+        result,
+        Err(LexerError::InvalidStructuredReference(_, _))
+    ));
+}
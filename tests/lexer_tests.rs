@@ -1,10 +1,18 @@
 mod lexer {
     mod test_arrays;
     mod test_booleans;
+    mod test_cells;
     mod test_edge_cases;
     mod test_errors;
+    mod test_external_refs;
+    mod test_functions;
     mod test_lexer_errors;
+    mod test_named_ranges;
     mod test_numbers;
     mod test_operators;
+    mod test_ranges;
+    mod test_sheets;
+    mod test_spans;
     mod test_strings;
+    mod test_structured_refs;
 }
@@ -0,0 +1,141 @@
+use expy::bindings::ast::{Expr, Literal, ParseError};
+use expy::bindings::lexer::Lexer;
+use expy::bindings::parser::Parser;
+use expy::bindings::token::Token;
+
+fn parse(formula: &str) -> Result<Expr, ParseError> {
+    let tokens = Lexer::new(formula).tokenize().unwrap();
+    Parser::new(tokens).parse()
+}
+
+fn rows(formula: &str) -> Vec<Vec<Expr>> {
+    match parse(formula).unwrap() {
+        Expr::Array(rows) => rows,
+        other => panic!("expected array, got {other:?}"),
+    }
+}
+
+// ============================================================================
+// SPEC: hArrayConsti - hConstanti | hUnOpPrefixi NUMBER | ERROR-REF
+// ============================================================================
+
+#[test]
+fn test_single_row() {
+    let rows = rows("={1,2,3}");
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].len(), 3);
+}
+
+#[test]
+fn test_rectangular() {
+    assert_eq!(
+        parse("={1,2;3,4;5,6}").unwrap().to_string(),
+        "{1,2;3,4;5,6}"
+    );
+}
+
+#[test]
+fn test_single_column() {
+    let rows = rows("={1;2;3}");
+    assert_eq!(rows.len(), 3);
+    assert!(rows.iter().all(|row| row.len() == 1));
+}
+
+#[test]
+fn test_signed_numbers() {
+    let rows = rows("={-1,+2,-0.5}");
+    assert_eq!(rows[0][0], Expr::Literal(Literal::Number(-1.0)));
+    assert_eq!(rows[0][1], Expr::Literal(Literal::Number(2.0)));
+    assert_eq!(rows[0][2], Expr::Literal(Literal::Number(-0.5)));
+}
+
+#[test]
+fn test_mixed_constants() {
+    let rows = rows("={\"a\",TRUE;#N/A,#REF!}");
+    assert_eq!(rows[0][0], Expr::Literal(Literal::String("a".into())));
+    assert_eq!(rows[0][1], Expr::Literal(Literal::Bool(true)));
+    assert_eq!(rows[1][0], Expr::Literal(Literal::Error("#N/A".into())));
+    assert_eq!(rows[1][1], Expr::Literal(Literal::Error("#REF!".into())));
+}
+
+// ============================================================================
+// SPEC: invalid array constants
+// ============================================================================
+
+#[test]
+fn test_ragged_short_row() {
+    let err = parse("={1,2;3}").unwrap_err();
+    assert!(matches!(
+        err,
+        ParseError::RaggedArray {
+            row: 2,
+            expected: 2,
+            found: 1,
+            ..
+        }
+    ));
+    assert_eq!(
+        err.to_string(),
+        "array row 2 has 1 value, expected 2 like the first row"
+    );
+    assert_eq!(err.span().start, 6);
+    assert_eq!(err.span().end, 7);
+}
+
+#[test]
+fn test_ragged_long_row() {
+    assert!(matches!(
+        parse("={1;2,3}"),
+        Err(ParseError::RaggedArray {
+            row: 2,
+            expected: 1,
+            found: 2,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_reference_element_rejected() {
+    assert!(matches!(
+        parse("={1,A1}"),
+        Err(ParseError::UnexpectedToken(token, _)) if matches!(*token, Token::Cell(_))
+    ));
+}
+
+#[test]
+fn test_expression_element_rejected() {
+    assert!(matches!(
+        parse("={1+2}"),
+        Err(ParseError::UnexpectedToken(token, _)) if *token == Token::Plus
+    ));
+}
+
+#[test]
+fn test_double_sign_rejected() {
+    assert!(matches!(
+        parse("={--1}"),
+        Err(ParseError::UnexpectedToken(token, _)) if *token == Token::Minus
+    ));
+}
+
+#[test]
+fn test_empty_element_rejected() {
+    assert!(matches!(
+        parse("={1,,2}"),
+        Err(ParseError::UnexpectedToken(token, _)) if *token == Token::Comma
+    ));
+}
+
+#[test]
+fn test_empty_array_rejected() {
+    assert!(matches!(
+        parse("={}"),
+        Err(ParseError::UnexpectedToken(token, _)) if *token == Token::RightBrace
+    ));
+}
+
+#[test]
+fn test_unclosed_array() {
+    assert!(matches!(parse("={1,2"), Err(ParseError::UnexpectedEnd(_))));
+}
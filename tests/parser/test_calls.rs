@@ -0,0 +1,91 @@
+use expy::bindings::ast::{CallKind, Expr};
+use expy::bindings::lexer::Lexer;
+use expy::bindings::parser::Parser;
+
+fn parse(formula: &str) -> Expr {
+    let tokens = Lexer::new(formula).tokenize().unwrap();
+    Parser::new(tokens).parse().unwrap()
+}
+
+fn call(formula: &str) -> (String, CallKind, Vec<Expr>) {
+    match parse(formula) {
+        Expr::Call { name, kind, args } => (name, kind, args),
+        other => panic!("expected call, got {other:?}"),
+    }
+}
+
+// ============================================================================
+// SPEC: function calls
+// ============================================================================
+
+#[test]
+fn test_no_arguments() {
+    let (name, kind, args) = call("=NOW()");
+    assert_eq!(name, "NOW");
+    assert_eq!(kind, CallKind::Builtin);
+    assert!(args.is_empty());
+}
+
+#[test]
+fn test_arguments() {
+    let (name, _, args) = call("=SUM(A1:A3,5,B1)");
+    assert_eq!(name, "SUM");
+    assert_eq!(args.len(), 3);
+}
+
+#[test]
+fn test_argument_expressions() {
+    assert_eq!(parse("=ROUND(A1*2,1+1)").to_string(), "ROUND((A1*2),(1+1))");
+}
+
+#[test]
+fn test_missing_arguments() {
+    let (_, _, args) = call("=IF(A1,,1)");
+    assert_eq!(args.len(), 3);
+    assert_eq!(args[1], Expr::Missing);
+}
+
+#[test]
+fn test_trailing_missing_argument() {
+    let (_, _, args) = call("=VLOOKUP(1,A:B,2,)");
+    assert_eq!(args.len(), 4);
+    assert_eq!(args[3], Expr::Missing);
+}
+
+#[test]
+fn test_nested_calls() {
+    assert_eq!(
+        parse("=IF(SUM(A1:A3)>10,MAX(B1,B2),0)").to_string(),
+        "IF((SUM(A1:A3)>10),MAX(B1,B2),0)"
+    );
+}
+
+#[test]
+fn test_call_kinds() {
+    assert_eq!(call("=INDEX(A:A,1)").1, CallKind::Reference);
+    assert_eq!(call("=CHOOSE(1,A1,B1)").1, CallKind::ConditionalReference);
+    assert_eq!(call("=_xll.Foo(1)").1, CallKind::UserDefined);
+}
+
+#[test]
+fn test_negated_call() {
+    assert_eq!(parse("=-ABS(A1)^2").to_string(), "((-ABS(A1))^2)");
+}
+
+// ============================================================================
+// SPEC: array constants
+// ============================================================================
+
+#[test]
+fn test_array_rows() {
+    let Expr::Array(rows) = parse("={1,2;3,4}") else {
+        panic!("expected array");
+    };
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].len(), 2);
+}
+
+#[test]
+fn test_array_in_call() {
+    assert_eq!(parse("=SUM({1,2,3})").to_string(), "SUM({1,2,3})");
+}
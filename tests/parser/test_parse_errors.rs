@@ -0,0 +1,159 @@
+use expy::bindings::ast::{Expr, ParseError};
+use expy::bindings::lexer::Lexer;
+use expy::bindings::parser::Parser;
+use expy::bindings::token::Token;
+
+fn parse(formula: &str) -> Result<Expr, ParseError> {
+    let tokens = Lexer::new(formula).tokenize().unwrap();
+    Parser::new(tokens).parse()
+}
+
+fn unexpected_token(formula: &str) -> Token {
+    match parse(formula) {
+        Err(ParseError::UnexpectedToken(token, _)) => *token,
+        other => panic!("expected unexpected token, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_empty_formula() {
+    assert!(matches!(parse("="), Err(ParseError::UnexpectedEnd(_))));
+    assert!(matches!(parse(""), Err(ParseError::UnexpectedEnd(_))));
+}
+
+#[test]
+fn test_dangling_operator() {
+    assert!(matches!(parse("=1+"), Err(ParseError::UnexpectedEnd(_))));
+}
+
+#[test]
+fn test_unclosed_paren() {
+    assert!(matches!(parse("=(1+2"), Err(ParseError::UnexpectedEnd(_))));
+}
+
+#[test]
+fn test_unclosed_call() {
+    assert!(matches!(
+        parse("=SUM(1,2"),
+        Err(ParseError::UnexpectedEnd(_))
+    ));
+}
+
+#[test]
+fn test_extra_close_paren() {
+    assert_eq!(unexpected_token("=1+2)"), Token::RightParen);
+}
+
+#[test]
+fn test_adjacent_values() {
+    assert_eq!(unexpected_token("=1 2"), Token::Number(2.0));
+}
+
+#[test]
+fn test_union_outside_parens() {
+    assert_eq!(unexpected_token("=A1,B1"), Token::Comma);
+}
+
+#[test]
+fn test_sheet_without_reference() {
+    assert_eq!(unexpected_token("=Sheet1!1"), Token::Number(1.0));
+}
+
+#[test]
+fn test_error_span() {
+    let err = parse("=1+*2").unwrap_err();
+    assert!(matches!(&err, ParseError::UnexpectedToken(token, _) if **token == Token::Multiply));
+    assert_eq!(err.span().start, 3);
+    assert_eq!(err.span().end, 4);
+}
+
+#[test]
+fn test_wrong_argument_count() {
+    let err = parse("=IFERROR(A1)").unwrap_err();
+    assert!(matches!(
+        &err,
+        ParseError::WrongArgumentCount { name, found: 1, .. } if name == "IFERROR"
+    ));
+    assert_eq!(err.to_string(), "IFERROR expects 2 arguments");
+    assert_eq!(
+        parse("=ISNA()").unwrap_err().to_string(),
+        "ISNA expects 1 argument"
+    );
+    assert_eq!(
+        parse("=NA(1)").unwrap_err().to_string(),
+        "NA expects 0 arguments"
+    );
+}
+
+#[test]
+fn test_variadic_argument_count() {
+    assert_eq!(
+        parse("=SUM()").unwrap_err().to_string(),
+        "SUM expects at least 1 argument"
+    );
+    let args = vec!["1"; 256].join(",");
+    assert!(matches!(
+        parse(&format!("=SUM({args})")),
+        Err(ParseError::WrongArgumentCount { found: 256, .. })
+    ));
+    assert!(parse(&format!("=SUM({})", vec!["1"; 255].join(","))).is_ok());
+}
+
+#[test]
+fn test_missing_arguments_count() {
+    assert!(matches!(
+        parse("=ISNA(,)"),
+        Err(ParseError::WrongArgumentCount { found: 2, .. })
+    ));
+}
+
+#[test]
+fn test_argument_count_span() {
+    let err = parse("=1+ISNA(A1,B1)").unwrap_err();
+    assert_eq!(err.span().start, 3);
+    assert_eq!(err.span().end, 14);
+}
+
+#[test]
+fn test_uncataloged_functions_are_not_checked() {
+    assert!(parse("=NOW(1,2,3)").is_ok());
+    assert!(parse("=_xll.SUM()").is_ok());
+}
+
+#[test]
+fn test_paired_argument_count() {
+    assert_eq!(
+        parse("=IFS(A1,1,B1)").unwrap_err().to_string(),
+        "IFS expects 2, 4, 6, ... arguments"
+    );
+    assert_eq!(
+        parse("=IF()").unwrap_err().to_string(),
+        "IF expects 2-3 arguments"
+    );
+}
+
+#[test]
+fn test_reference_arguments() {
+    let err = parse("=SUMIF({1,2},\">1\")").unwrap_err();
+    assert!(matches!(
+        &err,
+        ParseError::ExpectedReference { name, position: 1, .. } if name == "SUMIF"
+    ));
+    assert_eq!(err.to_string(), "SUMIF argument 1 must be a reference");
+    assert_eq!(
+        parse("=SUMIF(A1:A3,\">1\",5)").unwrap_err().to_string(),
+        "SUMIF argument 3 must be a reference"
+    );
+    assert_eq!(
+        parse("=SUMIFS(A1:A3,B1:B3,1,2,3)").unwrap_err().to_string(),
+        "SUMIFS argument 4 must be a reference"
+    );
+}
+
+#[test]
+fn test_reference_arguments_accept_reference_functions() {
+    assert!(parse("=COUNTIF(Sheet2!A:A,1)").is_ok());
+    assert!(parse("=COUNTIF(INDEX(A:B,0,1),1)").is_ok());
+    assert!(parse("=COUNTIF(IF(A1,B:B,C:C),1)").is_ok());
+    assert!(parse("=COUNTIF((A1:A3,B1:B3),1)").is_ok());
+}
@@ -0,0 +1,156 @@
+use expy::bindings::ast::Expr;
+use expy::bindings::lexer::Lexer;
+use expy::bindings::parser::Parser;
+
+fn parse(formula: &str) -> Expr {
+    let tokens = Lexer::new(formula).tokenize().unwrap();
+    Parser::new(tokens).parse().unwrap()
+}
+
+fn render(formula: &str) -> String {
+    parse(formula).to_string()
+}
+
+// ============================================================================
+// SPEC: arithmetic precedence and associativity
+// ============================================================================
+
+#[test]
+fn test_multiplication_before_addition() {
+    assert_eq!(render("=1+2*3"), "(1+(2*3))");
+    assert_eq!(render("=1*2+3"), "((1*2)+3)");
+}
+
+#[test]
+fn test_subtraction_left_associative() {
+    assert_eq!(render("=1-2-3"), "((1-2)-3)");
+}
+
+#[test]
+fn test_division_left_associative() {
+    assert_eq!(render("=8/4/2"), "((8/4)/2)");
+}
+
+#[test]
+fn test_power_left_associative() {
+    assert_eq!(render("=2^3^2"), "((2^3)^2)");
+}
+
+#[test]
+fn test_power_before_multiplication() {
+    assert_eq!(render("=2*3^2"), "(2*(3^2))");
+}
+
+#[test]
+fn test_parentheses_override() {
+    assert_eq!(render("=(1+2)*3"), "((1+2)*3)");
+}
+
+#[test]
+fn test_leading_equals_optional() {
+    assert_eq!(render("1+2"), render("=1+2"));
+}
+
+// ============================================================================
+// SPEC: prefix and postfix operators
+// ============================================================================
+
+#[test]
+fn test_negation_binds_tighter_than_power() {
+    assert_eq!(render("=-2^2"), "((-2)^2)");
+}
+
+#[test]
+fn test_negation_in_exponent() {
+    assert_eq!(render("=2^-1"), "(2^(-1))");
+}
+
+#[test]
+fn test_double_negation() {
+    assert_eq!(render("=--A1"), "(-(-A1))");
+}
+
+#[test]
+fn test_unary_plus() {
+    assert_eq!(render("=+A1"), "(+A1)");
+}
+
+#[test]
+fn test_percent_postfix() {
+    assert_eq!(render("=50%"), "(50%)");
+}
+
+#[test]
+fn test_percent_before_power() {
+    assert_eq!(render("=2^50%"), "(2^(50%))");
+}
+
+#[test]
+fn test_negation_before_percent() {
+    assert_eq!(render("=-5%"), "((-5)%)");
+}
+
+#[test]
+fn test_double_percent() {
+    assert_eq!(render("=5%%"), "((5%)%)");
+}
+
+// ============================================================================
+// SPEC: concatenation and comparison
+// ============================================================================
+
+#[test]
+fn test_concat_below_arithmetic() {
+    assert_eq!(render("=1+2&3"), "((1+2)&3)");
+    assert_eq!(render("=\"a\"&2*3"), "(\"a\"&(2*3))");
+}
+
+#[test]
+fn test_concat_left_associative() {
+    assert_eq!(render("=A1&\" \"&B1"), "((A1&\" \")&B1)");
+}
+
+#[test]
+fn test_comparison_lowest() {
+    assert_eq!(render("=A1=1&2"), "(A1=(1&2))");
+    assert_eq!(render("=A1+1>B1*2"), "((A1+1)>(B1*2))");
+}
+
+#[test]
+fn test_all_comparisons() {
+    for op in ["=", "<>", "<", ">", "<=", ">="] {
+        assert_eq!(render(&format!("=1{op}2")), format!("(1{op}2)"));
+    }
+}
+
+#[test]
+fn test_comparison_left_associative() {
+    assert_eq!(render("=1<2=TRUE"), "((1<2)=TRUE)");
+}
+
+// ============================================================================
+// SPEC: reference operators
+// ============================================================================
+
+#[test]
+fn test_range_before_negation() {
+    assert_eq!(render("=-A1:B2"), "(-A1:B2)");
+}
+
+#[test]
+fn test_range_before_intersection() {
+    assert_eq!(render("=A1:C3 B2:D4"), "(A1:C3 B2:D4)");
+}
+
+#[test]
+fn test_intersection_before_arithmetic() {
+    assert_eq!(render("=Jan_Sales Q1_Col+1"), "((Jan_Sales Q1_Col)+1)");
+}
+
+#[test]
+fn test_literals() {
+    assert_eq!(render("=\"say \"\"hi\"\"\""), "\"say \"\"hi\"\"\"");
+    assert_eq!(render("=TRUE"), "TRUE");
+    assert_eq!(render("=#N/A"), "#N/A");
+    assert_eq!(render("=2.5"), "2.5");
+}
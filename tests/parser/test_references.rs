@@ -0,0 +1,153 @@
+use expy::bindings::ast::{Expr, Reference, ReferenceKind, SheetRef};
+use expy::bindings::lexer::Lexer;
+use expy::bindings::parser::Parser;
+use expy::bindings::token::CellRef;
+
+fn parse(formula: &str) -> Expr {
+    let tokens = Lexer::new(formula).tokenize().unwrap();
+    Parser::new(tokens).parse().unwrap()
+}
+
+// ============================================================================
+// SPEC: cells and ranges
+// ============================================================================
+
+#[test]
+fn test_cell() {
+    let expr = parse("=A1");
+    assert_eq!(
+        expr,
+        Expr::Reference(Reference::local(ReferenceKind::Cell(CellRef::new(1, 1))))
+    );
+}
+
+#[test]
+fn test_cell_range() {
+    let expr = parse("=A1:$B$2");
+    assert!(matches!(expr, Expr::Range(..)));
+    assert_eq!(expr.to_string(), "A1:$B$2");
+}
+
+#[test]
+fn test_row_and_column_ranges() {
+    assert_eq!(parse("=1:5").to_string(), "1:5");
+    assert_eq!(parse("=$A:C").to_string(), "$A:C");
+}
+
+#[test]
+fn test_named_range() {
+    let expr = parse("=TaxRate");
+    assert_eq!(
+        expr,
+        Expr::Reference(Reference::local(ReferenceKind::Name("TaxRate".into())))
+    );
+}
+
+#[test]
+fn test_reserved_name() {
+    assert_eq!(parse("=_xlnm.Print_Area").to_string(), "_xlnm.Print_Area");
+}
+
+#[test]
+fn test_structured_ref() {
+    assert_eq!(parse("=Sales[Amount]").to_string(), "Sales[Amount]");
+}
+
+#[test]
+fn test_function_range() {
+    let expr = parse("=INDEX(A:A,3):B5");
+    assert_eq!(expr.to_string(), "INDEX(A:A,3):B5");
+    let Expr::Range(left, _) = expr else {
+        panic!("expected range");
+    };
+    assert!(matches!(*left, Expr::Call { .. }));
+}
+
+// ============================================================================
+// SPEC: sheet and workbook prefixes
+// ============================================================================
+
+#[test]
+fn test_sheet_cell() {
+    let Expr::Reference(reference) = parse("=Sheet2!A1") else {
+        panic!("expected reference");
+    };
+    assert_eq!(reference.sheet, Some(SheetRef::Single("Sheet2".into())));
+    assert_eq!(reference.kind, ReferenceKind::Cell(CellRef::new(1, 1)));
+}
+
+#[test]
+fn test_quoted_sheet() {
+    assert_eq!(parse("='Q1 Budget'!B4").to_string(), "'Q1 Budget'!B4");
+}
+
+#[test]
+fn test_multiple_sheets() {
+    let Expr::Reference(reference) = parse("=Jan:Dec!C3") else {
+        panic!("expected reference");
+    };
+    assert_eq!(
+        reference.sheet,
+        Some(SheetRef::Range("Jan".into(), "Dec".into()))
+    );
+}
+
+#[test]
+fn test_sheet_range_carries_sheet() {
+    let Expr::Range(_, right) = parse("=Sheet1!A1:B2") else {
+        panic!("expected range");
+    };
+    let Expr::Reference(end) = *right else {
+        panic!("expected reference");
+    };
+    assert_eq!(end.sheet, Some(SheetRef::Single("Sheet1".into())));
+}
+
+#[test]
+fn test_external_workbook() {
+    let Expr::Reference(reference) = parse("=[2]Prices!$B$3") else {
+        panic!("expected reference");
+    };
+    assert_eq!(reference.workbook, Some(2));
+    assert_eq!(reference.sheet, Some(SheetRef::Single("Prices".into())));
+    assert_eq!(reference.to_string(), "[2]Prices!$B$3");
+}
+
+#[test]
+fn test_external_workbook_name() {
+    let Expr::Reference(reference) = parse("=[1]!Rate") else {
+        panic!("expected reference");
+    };
+    assert_eq!(reference.workbook, Some(1));
+    assert_eq!(reference.sheet, None);
+    assert_eq!(reference.kind, ReferenceKind::Name("Rate".into()));
+}
+
+#[test]
+fn test_sheet_ref_error() {
+    assert_eq!(parse("=Sheet1!#REF!").to_string(), "#REF!");
+}
+
+#[test]
+fn test_dde_call() {
+    let Expr::Reference(reference) = parse("=MSEXCEL|'Sheet1'!R1C1") else {
+        panic!("expected reference");
+    };
+    assert!(matches!(reference.kind, ReferenceKind::Dde { .. }));
+}
+
+// ============================================================================
+// SPEC: union
+// ============================================================================
+
+#[test]
+fn test_union_in_parens() {
+    let expr = parse("=SUM((A1:A3,C1:C3))");
+    assert_eq!(expr.to_string(), "SUM((A1:A3,C1:C3))");
+}
+
+#[test]
+fn test_sheet_range_display() {
+    assert_eq!(parse("=Sheet1!A1:B2").to_string(), "Sheet1!A1:B2");
+    assert_eq!(parse("=[1]Prices!A1:B2").to_string(), "[1]Prices!A1:B2");
+}
@@ -0,0 +1,7 @@
+mod parser {
+    mod test_arrays;
+    mod test_calls;
+    mod test_parse_errors;
+    mod test_precedence;
+    mod test_references;
+}
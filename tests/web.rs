@@ -1,16 +1,39 @@
 #![cfg(target_arch = "wasm32")]
 
-use expy::add;
+use expy::{
+    ReferenceStyle, TranspileOptions, supported_functions_js, transpile_js, transpile_with_js,
+};
 use wasm_bindgen_test::*;
 
 wasm_bindgen_test_configure!(run_in_browser);
 
 #[wasm_bindgen_test]
-fn test_add() {
-    assert_eq!(add(1, 2), 3);
+fn test_transpile() {
+    assert_eq!(transpile_js("=A1").unwrap(), "xl(\"A1\")");
 }
 
 #[wasm_bindgen_test]
-fn test_add_zero() {
-    assert_eq!(add(0, 0), 0);
+fn test_transpile_error() {
+    assert!(transpile_js("=1+").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_transpile_with_reference_style() {
+    let mut options = TranspileOptions::new();
+    options.reference_style = ReferenceStyle::Openpyxl;
+    assert_eq!(
+        transpile_with_js("=A1", &options).unwrap(),
+        "ws[\"A1\"].value"
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_supported_functions() {
+    let functions = supported_functions_js();
+    let sum = functions.iter().find(|f| f.name() == "SUM").unwrap();
+    assert_eq!(sum.min_args(), 1);
+    assert_eq!(sum.max_args(), None);
+    assert_eq!(sum.category(), "Math");
+    assert_eq!(sum.arg_kinds(), ["Range..."]);
+    assert_eq!(sum.python(), "sum_");
 }